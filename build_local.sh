@@ -6,7 +6,7 @@ mkdir -p out
 
 perl -i -pe 's/\["cdylib", "rlib"\]/\["cdylib"\]/' Cargo.toml
 
-cargo build -p fungible-token --target wasm32-unknown-unknown --release
+cargo build -p fungible-token -p dao -p receiver --target wasm32-unknown-unknown --release
 cp target/wasm32-unknown-unknown/release/*.wasm ./out/
 
 perl -i -pe 's/\["cdylib"\]/\["cdylib", "rlib"\]/' Cargo.toml
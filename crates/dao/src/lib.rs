@@ -0,0 +1,178 @@
+//! Minimal stand-in for a Sputnik DAO, that is only used in the sandbox tests.
+//!
+//! It mirrors the parts of the Sputnik DAO v2 interface, that are needed to administer the
+//! token: council members add function call proposals via `add_proposal` and vote on them via
+//! `act_proposal`. Once a majority of the council approved a proposal, its actions are executed
+//! and the outcome is recorded in a callback.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{UnorderedSet, Vector};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{
+    env, near_bindgen, require, AccountId, Gas, PanicOnDefault, Promise, PromiseResult,
+};
+
+const GAS_FOR_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ActionCall {
+    pub method_name: String,
+    pub args: Base64VecU8,
+    pub deposit: U128,
+    pub gas: U64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ProposalKind {
+    FunctionCall {
+        receiver_id: AccountId,
+        actions: Vec<ActionCall>,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ProposalStatus {
+    InProgress,
+    Approved,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Action {
+    VoteApprove,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalInput {
+    pub description: String,
+    pub kind: ProposalKind,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Proposal {
+    pub proposer: AccountId,
+    pub description: String,
+    pub kind: ProposalKind,
+    pub status: ProposalStatus,
+    pub votes: Vec<AccountId>,
+}
+
+#[near_bindgen]
+#[derive(BorshSerialize, BorshDeserialize, PanicOnDefault)]
+pub struct Contract {
+    council: UnorderedSet<AccountId>,
+    proposals: Vector<Proposal>,
+}
+
+#[near_bindgen]
+impl Contract {
+    #[init]
+    pub fn new(council: Vec<AccountId>) -> Self {
+        require!(!env::state_exists(), "Already initialized");
+        require!(!council.is_empty(), "Council must not be empty");
+        let mut this = Self {
+            council: UnorderedSet::new(b"c"),
+            proposals: Vector::new(b"p"),
+        };
+        this.council.extend(council);
+        this
+    }
+
+    /// Adds a new proposal and returns its id. Can only be called by a council member.
+    pub fn add_proposal(&mut self, proposal: ProposalInput) -> u64 {
+        let proposer = self.assert_council();
+        self.proposals.push(&Proposal {
+            proposer,
+            description: proposal.description,
+            kind: proposal.kind,
+            status: ProposalStatus::InProgress,
+            votes: vec![],
+        });
+        self.proposals.len() - 1
+    }
+
+    /// Votes on a proposal and executes it, once a majority of the council approved.
+    /// Can only be called by a council member.
+    pub fn act_proposal(&mut self, id: u64, action: Action) {
+        let account_id = self.assert_council();
+        let mut proposal = self.proposals.get(id).expect("Proposal not found");
+        require!(
+            proposal.status == ProposalStatus::InProgress,
+            "Proposal is not in progress"
+        );
+        match action {
+            Action::VoteApprove => {
+                require!(!proposal.votes.contains(&account_id), "Already voted");
+                proposal.votes.push(account_id);
+            }
+        }
+
+        if proposal.votes.len() as u64 > self.council.len() / 2 {
+            proposal.status = ProposalStatus::Approved;
+            self.internal_execute(id, &proposal.kind);
+        }
+        self.proposals.replace(id, &proposal);
+    }
+
+    /// Marks the proposal as failed, if any of its actions failed.
+    #[private]
+    pub fn on_proposal_callback(&mut self, id: u64) {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            let mut proposal = self.proposals.get(id).expect("Proposal not found");
+            proposal.status = ProposalStatus::Failed;
+            self.proposals.replace(id, &proposal);
+        }
+    }
+
+    pub fn get_proposal(&self, id: u64) -> Option<Proposal> {
+        self.proposals.get(id)
+    }
+
+    pub fn get_council(&self) -> Vec<AccountId> {
+        self.council.to_vec()
+    }
+}
+
+impl Contract {
+    fn assert_council(&self) -> AccountId {
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.council.contains(&account_id),
+            "Only council members can call this method"
+        );
+        account_id
+    }
+
+    fn internal_execute(&self, id: u64, kind: &ProposalKind) {
+        match kind {
+            ProposalKind::FunctionCall {
+                receiver_id,
+                actions,
+            } => {
+                let promise =
+                    actions
+                        .iter()
+                        .fold(Promise::new(receiver_id.clone()), |promise, action| {
+                            promise.function_call(
+                                action.method_name.clone(),
+                                action.args.clone().into(),
+                                action.deposit.0,
+                                Gas(action.gas.0),
+                            )
+                        });
+                promise.then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_CALLBACK)
+                        .on_proposal_callback(id),
+                );
+            }
+        }
+    }
+}
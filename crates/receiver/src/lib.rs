@@ -0,0 +1,67 @@
+//! Minimal `ft_on_transfer` receiver, that is only used in the sandbox tests.
+//!
+//! The `msg` of `ft_transfer_call` tells the receiver, what to do with the received tokens:
+//! - `"<amount>"` keeps the tokens, except for the given amount, that is returned to the sender.
+//! - `"pause:<amount>"` additionally pauses the token, before the tokens are returned. The
+//!   receiver therefore needs the pauser role.
+//! - `"freeze:<amount>"` additionally freezes the sender on the token, before the tokens are
+//!   returned. The receiver therefore needs the freezer role.
+//! - Any other message fails, so that all of the tokens are returned.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::json;
+use near_sdk::{env, log, near_bindgen, AccountId, Gas, Promise, PromiseOrValue};
+
+const GAS_FOR_TOKEN_CALL: Gas = Gas(10_000_000_000_000);
+const GAS_FOR_CALLBACK: Gas = Gas(5_000_000_000_000);
+
+#[near_bindgen]
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub struct Contract {}
+
+#[near_bindgen]
+impl Contract {
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        log!("Received {} from {}", amount.0, sender_id);
+        let (call, unused) = match msg.split_once(':') {
+            Some(("pause", unused)) => (Some(("pause", json!({}))), unused),
+            Some(("freeze", unused)) => (
+                Some(("freeze_account", json!({ "account_id": sender_id }))),
+                unused,
+            ),
+            _ => (None, msg.as_str()),
+        };
+        let unused: u128 = unused.parse().expect("Invalid message");
+        assert!(unused <= amount.0, "Can't return more than the amount");
+        let (method_name, args) = match call {
+            Some(call) => call,
+            None => return PromiseOrValue::Value(U128(unused)),
+        };
+
+        Promise::new(env::predecessor_account_id())
+            .function_call(
+                method_name.to_string(),
+                args.to_string().into_bytes(),
+                1,
+                GAS_FOR_TOKEN_CALL,
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_CALLBACK)
+                    .on_token_call(U128(unused)),
+            )
+            .into()
+    }
+
+    /// Returns the unused amount, after the token has been called.
+    #[private]
+    pub fn on_token_call(&mut self, unused: U128) -> U128 {
+        unused
+    }
+}
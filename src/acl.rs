@@ -0,0 +1,153 @@
+use crate::event::{ContractEvent, RoleUpdate};
+use crate::{Contract, ContractExt, StorageKey};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId};
+
+/// Roles, that can be granted to accounts for calling privileged methods.
+/// The owner of the contract implicitly holds every role, until the multisig has been set up.
+///
+/// New roles must only ever be appended, because the Borsh discriminant is part of the
+/// storage key of the grantee sets.
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Can grant and revoke any role.
+    Admin,
+    Minter,
+    Burner,
+    Pauser,
+    Upgrader,
+    MetadataAdmin,
+    /// Can freeze and unfreeze accounts.
+    Freezer,
+    /// Can lock tokens of any account.
+    Locker,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Grants a role to the given account. Can only be called by the owner or an admin.
+    /// Returns `false`, if the account already had the role.
+    #[payable]
+    pub fn acl_grant_role(&mut self, role: Role, account_id: AccountId) -> bool {
+        assert_one_yocto();
+        self.assert_role(Role::Admin);
+
+        let mut grantees = self.internal_grantees(role);
+        if !grantees.insert(&account_id) {
+            return false;
+        }
+        self.acl.insert(&role, &grantees);
+
+        ContractEvent::RoleUpdate(RoleUpdate::Granted {
+            role,
+            account_id: &account_id,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+        true
+    }
+
+    /// Revokes a role from the given account. Can only be called by the owner or an admin.
+    /// Returns `false`, if the account did not have the role.
+    #[payable]
+    pub fn acl_revoke_role(&mut self, role: Role, account_id: AccountId) -> bool {
+        assert_one_yocto();
+        self.assert_role(Role::Admin);
+        self.internal_revoke_role(role, &account_id)
+    }
+
+    /// Removes a role from the caller.
+    /// Returns `false`, if the caller did not have the role.
+    pub fn acl_renounce_role(&mut self, role: Role) -> bool {
+        self.internal_revoke_role(role, &env::predecessor_account_id())
+    }
+
+    /// Returns whether the account holds the given role, either by being granted the role or by
+    /// being the owner of the contract. While a governance DAO is configured, only the DAO holds
+    /// any role.
+    pub fn acl_has_role(&self, role: Role, account_id: AccountId) -> bool {
+        self.has_role(role, &account_id)
+    }
+
+    /// Returns a page of accounts, that have been granted the given role.
+    pub fn acl_get_grantees(
+        &self,
+        role: Role,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        self.acl
+            .get(&role)
+            .map(|grantees| paginate(grantees.as_vector().iter(), from_index, limit))
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    pub(crate) fn has_role(&self, role: Role, account_id: &AccountId) -> bool {
+        if let Some(governance_id) = &self.governance_id {
+            return governance_id == account_id;
+        }
+        // once the multisig has been set up, privileged actions of the owner require the
+        // approval of the signers
+        (self.owner_id.as_ref() == Some(account_id) && !self.multisig.is_set_up())
+            || self
+                .acl
+                .get(&role)
+                .map(|grantees| grantees.contains(account_id))
+                .unwrap_or_default()
+    }
+
+    pub(crate) fn assert_role(&self, role: Role) {
+        require!(
+            self.has_role(role, &env::predecessor_account_id()),
+            format!("Caller is missing role {:?}", role)
+        );
+    }
+
+    fn internal_grantees(&self, role: Role) -> UnorderedSet<AccountId> {
+        self.acl.get(&role).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::AclGrantees { role }.try_to_vec().unwrap())
+        })
+    }
+
+    fn internal_revoke_role(&mut self, role: Role, account_id: &AccountId) -> bool {
+        let mut grantees = self.internal_grantees(role);
+        if !grantees.remove(account_id) {
+            return false;
+        }
+        self.acl.insert(&role, &grantees);
+
+        ContractEvent::RoleUpdate(RoleUpdate::Revoked {
+            role,
+            account_id,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+        true
+    }
+}
+
+/// Returns a page of the given items. Defaults to the first 100 items.
+pub(crate) fn paginate<T>(
+    items: impl Iterator<Item = T>,
+    from_index: Option<U128>,
+    limit: Option<u64>,
+) -> Vec<T> {
+    let from_index = from_index
+        .map(|from_index| from_index.0)
+        .unwrap_or_default();
+    let limit = limit.unwrap_or(100);
+    require!(limit != 0, "Cannot provide limit of 0");
+    items
+        .skip(from_index as usize)
+        .take(limit as usize)
+        .collect()
+}
@@ -0,0 +1,194 @@
+use crate::acl::paginate;
+use crate::event::{AirdropUpdate, ContractEvent};
+use crate::merkle::{self, MerkleProof};
+use crate::{Contract, ContractExt};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, Balance, CryptoHash};
+
+/// Number of claims, that are tracked by a single word of the claim bitmap.
+const CLAIMS_PER_WORD: u64 = 128;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AirdropCampaign {
+    pub merkle_root: Base58CryptoHash,
+    /// Amount of treasury tokens, that have been reserved for the campaign.
+    pub amount: U128,
+    pub claimed: U128,
+    /// Timestamp in nanoseconds, after which no more claims are accepted.
+    pub deadline: U64,
+    /// Whether the unclaimed tokens have been released back to the treasury.
+    pub swept: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Creates an airdrop campaign, that reserves `amount` tokens of the treasury for the claims
+    /// of the merkle tree with the given root. Returns the id of the campaign.
+    /// Can only be called by the owner.
+    #[payable]
+    pub fn airdrop_create(
+        &mut self,
+        merkle_root: Base58CryptoHash,
+        amount: U128,
+        deadline: U64,
+    ) -> u64 {
+        assert_one_yocto();
+        self.assert_owner();
+        self.internal_airdrop_create(merkle_root, amount, deadline)
+    }
+
+    /// Claims `amount` tokens of the campaign for the caller. The proof must lead from the leaf
+    /// of the caller's claim to the merkle root of the campaign.
+    pub fn airdrop_claim(&mut self, campaign_id: u64, amount: U128, proof: MerkleProof) {
+        let account_id = env::predecessor_account_id();
+        self.assert_not_paused();
+        self.assert_not_frozen(&account_id);
+        let mut campaign = self.internal_airdrop(campaign_id);
+        require!(
+            env::block_timestamp() <= campaign.deadline.0,
+            "Airdrop campaign has ended"
+        );
+        require!(
+            !self.internal_airdrop_claimed(campaign_id, proof.index),
+            "Airdrop has already been claimed"
+        );
+        require!(
+            merkle::verify(
+                &CryptoHash::from(campaign.merkle_root),
+                merkle::leaf_hash(proof.index, &account_id, amount.0),
+                &proof
+            ),
+            "Invalid merkle proof"
+        );
+        let claimed = campaign.claimed.0 + amount.0;
+        require!(
+            claimed <= campaign.amount.0,
+            "Airdrop campaign is exhausted"
+        );
+
+        self.internal_set_airdrop_claimed(campaign_id, proof.index);
+        campaign.claimed = claimed.into();
+        self.airdrops.replace(campaign_id, &campaign);
+        self.internal_release_treasury(amount.0);
+        self.internal_transfer(
+            &Self::treasury_id(),
+            &account_id,
+            amount.0,
+            Some(format!("Airdrop {}", campaign_id)),
+        );
+
+        ContractEvent::AirdropUpdate(AirdropUpdate::Claimed {
+            campaign_id,
+            index: proof.index,
+            account_id: &account_id,
+            amount,
+        })
+        .emit();
+    }
+
+    /// Releases the unclaimed tokens of an ended campaign back to the treasury.
+    /// Can only be called by the owner.
+    #[payable]
+    pub fn airdrop_sweep(&mut self, campaign_id: u64) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.internal_airdrop_sweep(campaign_id);
+    }
+
+    pub fn airdrop_campaign(&self, campaign_id: u64) -> Option<AirdropCampaign> {
+        self.airdrops.get(campaign_id)
+    }
+
+    /// Returns a page of all airdrop campaigns, ordered by their id.
+    pub fn airdrop_campaigns(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<AirdropCampaign> {
+        paginate(self.airdrops.iter(), from_index, limit)
+    }
+
+    /// Returns whether the claim with the given index of the campaign has been claimed.
+    pub fn airdrop_is_claimed(&self, campaign_id: u64, index: u64) -> bool {
+        self.internal_airdrop_claimed(campaign_id, index)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_airdrop_create(
+        &mut self,
+        merkle_root: Base58CryptoHash,
+        amount: U128,
+        deadline: U64,
+    ) -> u64 {
+        require!(amount.0 > 0, "The amount should be a positive number");
+        require!(
+            deadline.0 > env::block_timestamp(),
+            "Deadline must be in the future"
+        );
+        self.internal_reserve_treasury(amount.0);
+
+        let campaign = AirdropCampaign {
+            merkle_root,
+            amount,
+            claimed: U128(0),
+            deadline,
+            swept: false,
+        };
+        let campaign_id = self.airdrops.len();
+        self.airdrops.push(&campaign);
+
+        ContractEvent::AirdropUpdate(AirdropUpdate::Created {
+            campaign_id,
+            campaign: &campaign,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+        campaign_id
+    }
+
+    pub(crate) fn internal_airdrop_sweep(&mut self, campaign_id: u64) {
+        let mut campaign = self.internal_airdrop(campaign_id);
+        require!(
+            env::block_timestamp() > campaign.deadline.0,
+            "Airdrop campaign has not yet ended"
+        );
+        require!(!campaign.swept, "Airdrop campaign has already been swept");
+
+        let unclaimed: Balance = campaign.amount.0 - campaign.claimed.0;
+        campaign.swept = true;
+        self.airdrops.replace(campaign_id, &campaign);
+        self.internal_release_treasury(unclaimed);
+
+        ContractEvent::AirdropUpdate(AirdropUpdate::Swept {
+            campaign_id,
+            amount: unclaimed.into(),
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+
+    fn internal_airdrop(&self, campaign_id: u64) -> AirdropCampaign {
+        self.airdrops
+            .get(campaign_id)
+            .unwrap_or_else(|| env::panic_str("Airdrop campaign not found"))
+    }
+
+    fn internal_airdrop_claimed(&self, campaign_id: u64, index: u64) -> bool {
+        let word = self
+            .airdrop_claims
+            .get(&(campaign_id, index / CLAIMS_PER_WORD))
+            .unwrap_or_default();
+        word & (1u128 << (index % CLAIMS_PER_WORD)) != 0
+    }
+
+    fn internal_set_airdrop_claimed(&mut self, campaign_id: u64, index: u64) {
+        let key = (campaign_id, index / CLAIMS_PER_WORD);
+        let word = self.airdrop_claims.get(&key).unwrap_or_default();
+        self.airdrop_claims
+            .insert(&key, &(word | (1u128 << (index % CLAIMS_PER_WORD))));
+    }
+}
@@ -0,0 +1,214 @@
+//! Allowances, that let a spender transfer tokens on behalf of the owner via `ft_transfer_from`.
+//!
+//! Allowances can only be set via `ft_approve` while no allowance exists. Existing allowances are
+//! changed via `ft_increase_allowance` and `ft_decrease_allowance`, so that a spender can't use
+//! both the old and the new allowance by front-running an approval. The account, that creates an
+//! allowance, pays for its storage and gets the storage deposit refunded, once the allowance is
+//! removed. This is the owner, unless a relayer submitted a permit for the owner.
+
+use crate::acl::paginate;
+use crate::event::{AllowanceUpdate, ContractEvent};
+use crate::{Contract, ContractExt, StorageKey};
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::collections::UnorderedMap;
+use near_sdk::json_types::U128;
+use near_sdk::{
+    assert_one_yocto, env, near_bindgen, require, AccountId, Balance, Promise, StorageUsage,
+};
+
+#[near_bindgen]
+impl Contract {
+    /// Approves the spender to transfer up to `amount` tokens of the caller. Fails, if the
+    /// spender already has an allowance, which needs to be changed via `ft_increase_allowance` or
+    /// `ft_decrease_allowance` instead.
+    ///
+    /// Enough deposit for the storage of the allowance needs to be attached, at least 1 yoctoNEAR.
+    /// Any deposit, that is not needed, is refunded.
+    #[payable]
+    pub fn ft_approve(&mut self, spender_id: AccountId, amount: U128) {
+        let owner_id = env::predecessor_account_id();
+        require!(amount.0 > 0, "The amount should be a positive number");
+        require!(
+            self.internal_allowance(&owner_id, &spender_id) == 0,
+            "Allowance already exists, use ft_increase_allowance or ft_decrease_allowance"
+        );
+        self.internal_set_allowance_paid(&owner_id, &spender_id, amount.into());
+    }
+
+    /// Increases the allowance of the spender by `amount` tokens of the caller.
+    ///
+    /// Enough deposit for the storage of a new allowance needs to be attached, at least 1
+    /// yoctoNEAR. Any deposit, that is not needed, is refunded.
+    #[payable]
+    pub fn ft_increase_allowance(&mut self, spender_id: AccountId, amount: U128) {
+        let owner_id = env::predecessor_account_id();
+        require!(amount.0 > 0, "The amount should be a positive number");
+        let allowance = self
+            .internal_allowance(&owner_id, &spender_id)
+            .checked_add(amount.into())
+            .unwrap_or_else(|| env::panic_str("Allowance overflow"));
+        self.internal_set_allowance_paid(&owner_id, &spender_id, allowance);
+    }
+
+    /// Decreases the allowance of the spender by `amount` tokens of the caller. The allowance is
+    /// removed, if it drops to zero. Requires exactly 1 yoctoNEAR attached.
+    #[payable]
+    pub fn ft_decrease_allowance(&mut self, spender_id: AccountId, amount: U128) {
+        assert_one_yocto();
+        let owner_id = env::predecessor_account_id();
+        let allowance = self.internal_allowance(&owner_id, &spender_id);
+        require!(allowance > 0, "Allowance does not exist");
+        let allowance = allowance.saturating_sub(amount.into());
+        self.internal_set_allowance(&owner_id, &spender_id, allowance);
+    }
+
+    /// Transfers tokens of `owner_id` to the receiver, by using the allowance of the caller.
+    /// The transfer fee is charged like on `ft_transfer` and is part of the used allowance.
+    /// Requires exactly 1 yoctoNEAR attached.
+    #[payable]
+    pub fn ft_transfer_from(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        let spender_id = env::predecessor_account_id();
+        self.assert_not_frozen(&spender_id);
+        self.assert_transfer_allowed(&owner_id, &receiver_id);
+        let amount: Balance = amount.into();
+        let allowance = self
+            .internal_allowance(&owner_id, &spender_id)
+            .checked_sub(amount)
+            .unwrap_or_else(|| env::panic_str("Allowance exceeded"));
+        self.internal_set_allowance(&owner_id, &spender_id, allowance);
+        self.internal_transfer_with_fee(&owner_id, &receiver_id, amount, memo);
+    }
+
+    /// Returns the amount of tokens of `owner_id`, that the spender is allowed to transfer.
+    pub fn ft_allowance(&self, owner_id: AccountId, spender_id: AccountId) -> U128 {
+        self.internal_allowance(&owner_id, &spender_id).into()
+    }
+
+    /// Returns a page of all spenders and their allowances of the given owner.
+    pub fn ft_allowances_of(
+        &self,
+        owner_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<(AccountId, U128)> {
+        self.allowances
+            .get(&owner_id)
+            .map(|allowances| {
+                paginate(
+                    allowances
+                        .iter()
+                        .map(|(spender_id, allowance)| (spender_id, allowance.into())),
+                    from_index,
+                    limit,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_allowance(
+        &self,
+        owner_id: &AccountId,
+        spender_id: &AccountId,
+    ) -> Balance {
+        self.allowances
+            .get(owner_id)
+            .and_then(|allowances| allowances.get(spender_id))
+            .unwrap_or_default()
+    }
+
+    /// Sets the allowance and charges the attached deposit for any additional storage. The caller
+    /// is recorded as the payer of a new allowance, if it is not the owner.
+    pub(crate) fn internal_set_allowance_paid(
+        &mut self,
+        owner_id: &AccountId,
+        spender_id: &AccountId,
+        allowance: Balance,
+    ) {
+        require!(
+            env::attached_deposit() >= 1,
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        let storage_usage = env::storage_usage();
+        let payer_id = env::predecessor_account_id();
+        if &payer_id != owner_id && self.internal_allowance(owner_id, spender_id) == 0 {
+            self.allowance_payers
+                .insert(&(owner_id.clone(), spender_id.clone()), &payer_id);
+        }
+        self.internal_set_allowance(owner_id, spender_id, allowance);
+        refund_deposit(storage_usage);
+    }
+
+    /// Sets the allowance and removes it, if it is zero. The storage deposit of a removed
+    /// allowance is refunded to the account, that paid for it.
+    pub(crate) fn internal_set_allowance(
+        &mut self,
+        owner_id: &AccountId,
+        spender_id: &AccountId,
+        allowance: Balance,
+    ) {
+        let storage_usage = env::storage_usage();
+        let mut payer_id = owner_id.clone();
+        let mut allowances = self.allowances.get(owner_id).unwrap_or_else(|| {
+            UnorderedMap::new(
+                StorageKey::AllowancesOf {
+                    account_hash: env::sha256_array(owner_id.as_bytes()),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        if allowance > 0 {
+            allowances.insert(spender_id, &allowance);
+            self.allowances.insert(owner_id, &allowances);
+        } else {
+            allowances.remove(spender_id);
+            payer_id = self
+                .allowance_payers
+                .remove(&(owner_id.clone(), spender_id.clone()))
+                .unwrap_or(payer_id);
+            if allowances.is_empty() {
+                self.allowances.remove(owner_id);
+            } else {
+                self.allowances.insert(owner_id, &allowances);
+            }
+        }
+
+        ContractEvent::AllowanceUpdate(AllowanceUpdate {
+            owner_id,
+            spender_id,
+            allowance: allowance.into(),
+        })
+        .emit();
+
+        if env::storage_usage() < storage_usage {
+            let refund =
+                Balance::from(storage_usage - env::storage_usage()) * env::storage_byte_cost();
+            Promise::new(payer_id).transfer(refund);
+        }
+    }
+}
+
+/// Charges the attached deposit for the storage, that has been used since `storage_usage`,
+/// and refunds the rest to the caller.
+pub(crate) fn refund_deposit(storage_usage: StorageUsage) {
+    let required = Balance::from(env::storage_usage().saturating_sub(storage_usage))
+        * env::storage_byte_cost();
+    let attached = env::attached_deposit();
+    require!(
+        attached >= required,
+        format!("Must attach {} yoctoNEAR to cover storage", required)
+    );
+    let refund = attached - required;
+    if refund > 0 {
+        Promise::new(env::predecessor_account_id()).transfer(refund);
+    }
+}
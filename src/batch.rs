@@ -0,0 +1,97 @@
+use crate::{Contract, ContractExt};
+use near_contract_standards::fungible_token::events::FtTransfer;
+use near_sdk::json_types::U128;
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId, Balance};
+
+/// Maximum number of transfers in a single batch, so that a batch fits into the gas limit.
+const MAX_BATCH_TRANSFERS: usize = 100;
+
+#[near_bindgen]
+impl Contract {
+    /// Transfers tokens of the caller to many receivers at once. Every transfer is given as a
+    /// tuple of the receiver, the amount and an optional memo. Requires exactly 1 yoctoNEAR
+    /// attached.
+    ///
+    /// All transfers are validated before any tokens are moved, so either all or none of the
+    /// transfers are executed. The transfer fee is charged on every transfer like on
+    /// `ft_transfer`. A single `ft_transfer` event is logged for all transfers and fees.
+    #[payable]
+    pub fn ft_batch_transfer(&mut self, transfers: Vec<(AccountId, U128, Option<String>)>) {
+        assert_one_yocto();
+        require!(!transfers.is_empty(), "No transfers given");
+        require!(
+            transfers.len() <= MAX_BATCH_TRANSFERS,
+            format!(
+                "Cannot transfer to more than {} receivers at once",
+                MAX_BATCH_TRANSFERS
+            )
+        );
+        let sender_id = env::predecessor_account_id();
+
+        let mut total: Balance = 0;
+        for (index, (receiver_id, amount, _)) in transfers.iter().enumerate() {
+            if !self.token.accounts.contains_key(receiver_id) {
+                env::panic_str(&format!(
+                    "Transfer {}: receiver {} is not registered",
+                    index, receiver_id
+                ));
+            }
+            require!(
+                receiver_id != &sender_id,
+                format!(
+                    "Transfer {}: sender and receiver should be different",
+                    index
+                )
+            );
+            require!(
+                amount.0 > 0,
+                format!("Transfer {}: the amount should be a positive number", index)
+            );
+            self.assert_transfer_allowed(&sender_id, receiver_id);
+            total = total
+                .checked_add(amount.0)
+                .unwrap_or_else(|| env::panic_str("Total amount overflow"));
+        }
+
+        self.assert_spendable(&sender_id, total);
+
+        // the net amount of every transfer followed by its fee, if any
+        let mut legs: Vec<(AccountId, U128, Option<String>)> = Vec::with_capacity(transfers.len());
+        for (receiver_id, amount, memo) in transfers {
+            match self.internal_fee(&sender_id, &receiver_id, amount.0) {
+                Some((fee_receiver_id, fee)) => {
+                    self.assert_transfer_allowed(&sender_id, &fee_receiver_id);
+                    legs.push((receiver_id, (amount.0 - fee).into(), memo));
+                    legs.push((
+                        fee_receiver_id,
+                        fee.into(),
+                        Some("Transfer fee".to_string()),
+                    ));
+                }
+                None => legs.push((receiver_id, amount, memo)),
+            }
+        }
+
+        let account_ids: Vec<&AccountId> = std::iter::once(&sender_id)
+            .chain(legs.iter().map(|(receiver_id, _, _)| receiver_id))
+            .collect();
+        self.with_checkpoints(&account_ids, |this| {
+            this.token.internal_withdraw(&sender_id, total);
+            for (receiver_id, amount, _) in &legs {
+                this.token.internal_deposit(receiver_id, amount.0);
+            }
+        });
+
+        FtTransfer::emit_many(
+            &legs
+                .iter()
+                .map(|(receiver_id, amount, memo)| FtTransfer {
+                    old_owner_id: &sender_id,
+                    new_owner_id: receiver_id,
+                    amount,
+                    memo: memo.as_deref(),
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+}
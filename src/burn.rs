@@ -0,0 +1,68 @@
+use crate::acl::Role;
+use crate::{Contract, ContractExt};
+use near_contract_standards::fungible_token::events::FtBurn;
+use near_sdk::json_types::U128;
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId, Balance};
+
+#[near_bindgen]
+impl Contract {
+    /// Burns the given amount of tokens from the balance of the caller.
+    #[payable]
+    pub fn ft_burn(&mut self, amount: U128, memo: Option<String>) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        self.assert_not_frozen(&account_id);
+        self.assert_spendable(&account_id, amount.0);
+        self.internal_burn(&account_id, amount.into(), memo.as_deref());
+    }
+
+    /// Burns the given amount of tokens from the treasury, which is the balance of the
+    /// contract account itself. Reserved treasury tokens can't be burned.
+    /// Can only be called by a burner.
+    #[payable]
+    pub fn ft_burn_from(&mut self, amount: U128, memo: Option<String>) {
+        assert_one_yocto();
+        self.assert_role(Role::Burner);
+        self.assert_treasury_available(amount.into());
+        let account_id = Self::treasury_id();
+        self.internal_burn(&account_id, amount.into(), memo.as_deref());
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_burn(
+        &mut self,
+        account_id: &AccountId,
+        amount: Balance,
+        memo: Option<&str>,
+    ) {
+        require!(amount > 0, "The amount should be a positive number");
+        self.with_checkpoints(&[account_id], |this| {
+            this.token.internal_withdraw(account_id, amount)
+        });
+
+        FtBurn {
+            owner_id: account_id,
+            amount: &U128(amount),
+            memo,
+        }
+        .emit();
+    }
+
+    /// Called by `storage_unregister`, whenever an account is closed. A positive balance means,
+    /// that the account was force closed and its tokens have been burned.
+    pub(crate) fn on_account_closed(&mut self, account_id: AccountId, balance: Balance) {
+        self.internal_remove_lockups(&account_id);
+        if balance > 0 {
+            self.internal_record_balance(&account_id, balance, 0);
+            self.internal_record_total_supply(self.token.total_supply + balance);
+            FtBurn {
+                owner_id: &account_id,
+                amount: &U128(balance),
+                memo: Some("Account closed"),
+            }
+            .emit();
+        }
+        self.delegates.remove(&account_id);
+    }
+}
@@ -0,0 +1,210 @@
+//! Historical balances and total supply, that are recorded as checkpoints on every change.
+//!
+//! Every method, that changes balances or the total supply, runs the change via
+//! [`Contract::with_checkpoints`], which compares the values before and after the change. Only
+//! the last change within a block is kept. The first checkpoint of an account or of the supply
+//! is preceded by a checkpoint at block height 0 with the previous value, so that values from
+//! before checkpoints were recorded are still reported correctly.
+//!
+//! The full history is kept. The storage of new checkpoints is paid from the storage balance of
+//! the account, that causes the change (see [`crate::storage`]).
+
+use crate::{Contract, ContractExt, StorageKey};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::Vector;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near_bindgen, require, AccountId, Balance, BlockHeight};
+use std::collections::HashSet;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug)]
+pub struct Checkpoint {
+    pub block_height: BlockHeight,
+    pub timestamp: u64,
+    pub value: Balance,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Returns the balance of the account at the end of the given block.
+    pub fn ft_balance_of_at(&self, account_id: AccountId, block_height: U64) -> U128 {
+        assert_not_future(block_height.0, env::block_height());
+        self.internal_balance_at(&account_id, |checkpoint| {
+            checkpoint.block_height <= block_height.0
+        })
+        .into()
+    }
+
+    /// Returns the balance of the account at the given timestamp in nanoseconds.
+    pub fn ft_balance_of_at_timestamp(&self, account_id: AccountId, timestamp: U64) -> U128 {
+        assert_not_future(timestamp.0, env::block_timestamp());
+        self.internal_balance_at(&account_id, |checkpoint| {
+            checkpoint.timestamp <= timestamp.0
+        })
+        .into()
+    }
+
+    /// Returns the total supply at the end of the given block.
+    pub fn ft_total_supply_at(&self, block_height: U64) -> U128 {
+        assert_not_future(block_height.0, env::block_height());
+        self.internal_total_supply_at(|checkpoint| checkpoint.block_height <= block_height.0)
+            .into()
+    }
+
+    /// Returns the total supply at the given timestamp in nanoseconds.
+    pub fn ft_total_supply_at_timestamp(&self, timestamp: U64) -> U128 {
+        assert_not_future(timestamp.0, env::block_timestamp());
+        self.internal_total_supply_at(|checkpoint| checkpoint.timestamp <= timestamp.0)
+            .into()
+    }
+}
+
+impl Contract {
+    /// Runs the given change and records checkpoints of the given accounts and of the total
+    /// supply, if their values have changed. All accounts, whose balance may be changed, must be
+    /// given. Accounts, that are given more than once, are only recorded once. The storage of the
+    /// new checkpoints is charged to the caller.
+    pub(crate) fn with_checkpoints<R>(
+        &mut self,
+        account_ids: &[&AccountId],
+        change: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let mut seen = HashSet::new();
+        let unique_account_ids: Vec<&AccountId> = account_ids
+            .iter()
+            .copied()
+            .filter(|account_id| seen.insert(*account_id))
+            .collect();
+        let balances: Vec<Balance> = unique_account_ids
+            .iter()
+            .map(|account_id| self.token.accounts.get(account_id).unwrap_or_default())
+            .collect();
+        let total_supply = self.token.total_supply;
+
+        let result = change(self);
+
+        let storage_usage = env::storage_usage();
+        for (account_id, previous) in unique_account_ids.into_iter().zip(balances) {
+            let balance = self.token.accounts.get(account_id).unwrap_or_default();
+            self.internal_record_balance(account_id, previous, balance);
+        }
+        self.internal_record_total_supply(total_supply);
+        self.internal_charge_storage(storage_usage);
+        result
+    }
+
+    pub(crate) fn internal_record_balance(
+        &mut self,
+        account_id: &AccountId,
+        previous: Balance,
+        balance: Balance,
+    ) {
+        if previous == balance {
+            return;
+        }
+        let mut checkpoints = self.internal_balance_checkpoints(account_id);
+        record(&mut checkpoints, previous, balance);
+        self.balance_checkpoints.insert(account_id, &checkpoints);
+        self.internal_update_holder(account_id, previous, balance);
+
+        let delegatee = self.delegates.get(account_id);
+        if balance > previous {
+            self.internal_move_votes(None, delegatee.as_ref(), balance - previous);
+        } else {
+            self.internal_move_votes(delegatee.as_ref(), None, previous - balance);
+        }
+    }
+
+    pub(crate) fn internal_record_total_supply(&mut self, previous: Balance) {
+        let total_supply = self.token.total_supply;
+        if previous != total_supply {
+            record(&mut self.supply_checkpoints, previous, total_supply);
+        }
+    }
+
+    fn internal_balance_checkpoints(&self, account_id: &AccountId) -> Vector<Checkpoint> {
+        self.balance_checkpoints.get(account_id).unwrap_or_else(|| {
+            Vector::new(
+                StorageKey::BalanceCheckpointsOf {
+                    account_hash: env::sha256_array(account_id.as_bytes()),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        })
+    }
+
+    fn internal_balance_at(
+        &self,
+        account_id: &AccountId,
+        is_before: impl Fn(&Checkpoint) -> bool,
+    ) -> Balance {
+        match self.balance_checkpoints.get(account_id) {
+            Some(checkpoints) => value_at(&checkpoints, is_before),
+            None => self.token.accounts.get(account_id).unwrap_or_default(),
+        }
+    }
+
+    fn internal_total_supply_at(&self, is_before: impl Fn(&Checkpoint) -> bool) -> Balance {
+        if self.supply_checkpoints.is_empty() {
+            self.token.total_supply
+        } else {
+            value_at(&self.supply_checkpoints, is_before)
+        }
+    }
+}
+
+pub(crate) fn assert_not_future(at: u64, now: u64) {
+    require!(at <= now, "Cannot query the future");
+}
+
+/// Records the changed value in the current block.
+pub(crate) fn record(checkpoints: &mut Vector<Checkpoint>, previous: Balance, value: Balance) {
+    if checkpoints.is_empty() && previous != 0 {
+        checkpoints.push(&Checkpoint {
+            block_height: 0,
+            timestamp: 0,
+            value: previous,
+        });
+    }
+    let checkpoint = Checkpoint {
+        block_height: env::block_height(),
+        timestamp: env::block_timestamp(),
+        value,
+    };
+    match checkpoints.len().checked_sub(1) {
+        Some(last) if checkpoints.get(last).unwrap().block_height == checkpoint.block_height => {
+            checkpoints.replace(last, &checkpoint);
+        }
+        _ => checkpoints.push(&checkpoint),
+    }
+}
+
+/// Returns the value of the latest checkpoint or 0, if there is none.
+pub(crate) fn last_value(checkpoints: &Vector<Checkpoint>) -> Balance {
+    match checkpoints.len().checked_sub(1) {
+        Some(last) => checkpoints.get(last).unwrap().value,
+        None => 0,
+    }
+}
+
+/// Returns the value of the last checkpoint, that satisfies `is_before`, via binary search.
+/// Returns 0, if there is no such checkpoint.
+pub(crate) fn value_at(
+    checkpoints: &Vector<Checkpoint>,
+    is_before: impl Fn(&Checkpoint) -> bool,
+) -> Balance {
+    // number of checkpoints, that satisfy `is_before`
+    let (mut low, mut high) = (0, checkpoints.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if is_before(&checkpoints.get(mid).unwrap()) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    match low.checked_sub(1) {
+        Some(index) => checkpoints.get(index).unwrap().value,
+        None => 0,
+    }
+}
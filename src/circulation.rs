@@ -0,0 +1,124 @@
+//! Circulating supply, which is the total supply without the tokens of excluded accounts, locked
+//! tokens and tokens, that are held in escrow for vesting schedules.
+
+use crate::acl::{paginate, Role};
+use crate::event::{CirculationUpdate, ContractEvent};
+use crate::{Contract, ContractExt};
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId, Balance};
+
+/// Maximum number of excluded accounts, so that `ft_circulating_supply` stays cheap to compute.
+const MAX_EXCLUDED_ACCOUNTS: u64 = 100;
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CirculatingSupply {
+    pub total_supply: U128,
+    /// Balances of the excluded accounts.
+    pub excluded_accounts: Vec<(AccountId, U128)>,
+    /// Locked tokens of accounts, that are not excluded.
+    pub locked: U128,
+    /// Unclaimed tokens of vesting schedules, unless the treasury holding them is excluded.
+    pub vesting: U128,
+    pub circulating_supply: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Excludes the tokens of the given account from the circulating supply, e.g. of the treasury.
+    /// Can only be called by an admin.
+    #[payable]
+    pub fn exclude_from_circulation(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_role(Role::Admin);
+        require!(
+            self.circulation_excluded.len() < MAX_EXCLUDED_ACCOUNTS,
+            format!(
+                "Cannot exclude more than {} accounts",
+                MAX_EXCLUDED_ACCOUNTS
+            )
+        );
+        require!(
+            self.circulation_excluded.insert(&account_id),
+            "Account is already excluded"
+        );
+        Self::emit_circulation_update(&account_id, true);
+    }
+
+    /// Includes the tokens of a previously excluded account in the circulating supply again.
+    /// Can only be called by an admin.
+    #[payable]
+    pub fn include_in_circulation(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_role(Role::Admin);
+        require!(
+            self.circulation_excluded.remove(&account_id),
+            "Account is not excluded"
+        );
+        Self::emit_circulation_update(&account_id, false);
+    }
+
+    /// Returns a page of all accounts, that are excluded from the circulating supply.
+    pub fn circulation_excluded_accounts(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        paginate(
+            self.circulation_excluded.as_vector().iter(),
+            from_index,
+            limit,
+        )
+    }
+
+    /// Returns the circulating supply together with the amounts, that have been subtracted from
+    /// the total supply. Runs in constant time apart from the bounded number of excluded accounts.
+    pub fn ft_circulating_supply(&self) -> CirculatingSupply {
+        let mut excluded_locked: Balance = 0;
+        let excluded_accounts: Vec<(AccountId, Balance)> = self
+            .circulation_excluded
+            .iter()
+            .map(|account_id| {
+                excluded_locked += self.internal_locked_balance(&account_id);
+                let balance = self.token.accounts.get(&account_id).unwrap_or_default();
+                (account_id, balance)
+            })
+            .collect();
+        let excluded: Balance = excluded_accounts.iter().map(|(_, balance)| balance).sum();
+
+        // locked tokens of excluded accounts are already part of their balances
+        let locked = self.internal_total_locked().saturating_sub(excluded_locked);
+
+        let vesting: Balance = if self.circulation_excluded.contains(&Self::treasury_id()) {
+            0
+        } else {
+            self.vesting_outstanding
+        };
+
+        let total_supply = self.token.total_supply;
+        CirculatingSupply {
+            total_supply: total_supply.into(),
+            excluded_accounts: excluded_accounts
+                .into_iter()
+                .map(|(account_id, balance)| (account_id, balance.into()))
+                .collect(),
+            locked: locked.into(),
+            vesting: vesting.into(),
+            circulating_supply: total_supply
+                .saturating_sub(excluded + locked + vesting)
+                .into(),
+        }
+    }
+}
+
+impl Contract {
+    fn emit_circulation_update(account_id: &AccountId, excluded: bool) {
+        ContractEvent::CirculationUpdate(CirculationUpdate {
+            account_id,
+            excluded,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+}
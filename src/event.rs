@@ -0,0 +1,301 @@
+//! Contract specific events, which are logged in the NEP-297 events format:
+//! <https://github.com/near/NEPs/blob/master/specs/Standards/EventsFormat.md>
+//!
+//! Token transfers, mints and burns are still logged with the NEP-141 events from
+//! [`near_contract_standards::fungible_token::events`]. Everything else, that indexers might be
+//! interested in, is logged under the `walc` standard.
+
+use crate::acl::Role;
+use crate::airdrop::AirdropCampaign;
+use crate::fee::TransferFee;
+use crate::upgrade::PendingUpgrade;
+use crate::vesting::VestingSchedule;
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
+use near_sdk::serde::Serialize;
+use near_sdk::{env, serde_json, AccountId, PublicKey};
+
+const EVENT_STANDARD: &str = "walc";
+const EVENT_VERSION: &str = "1.0.0";
+
+#[must_use = "don't forget to `.emit()` this event"]
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)]
+pub enum ContractEvent<'a> {
+    MetadataUpdate(MetadataUpdate<'a>),
+    OwnerUpdate(OwnerUpdate<'a>),
+    RoleUpdate(RoleUpdate<'a>),
+    PauseUpdate(PauseUpdate<'a>),
+    FreezeUpdate(FreezeUpdate<'a>),
+    UpgradeUpdate(UpgradeUpdate<'a>),
+    ContractUpgrade(ContractUpgrade<'a>),
+    MultisigUpdate(MultisigUpdate<'a>),
+    GovernanceUpdate(GovernanceUpdate<'a>),
+    AllowanceUpdate(AllowanceUpdate<'a>),
+    PermitKeyUpdate(PermitKeyUpdate<'a>),
+    AirdropUpdate(AirdropUpdate<'a>),
+    VestingUpdate(VestingUpdate<'a>),
+    LockupUpdate(LockupUpdate<'a>),
+    DelegateChanged(DelegateChanged<'a>),
+    DelegateVotesChanged(DelegateVotesChanged<'a>),
+    CirculationUpdate(CirculationUpdate<'a>),
+    TransferFeeUpdate(TransferFeeUpdate<'a>),
+    FeeExemptionUpdate(FeeExemptionUpdate<'a>),
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MetadataUpdate<'a> {
+    pub updated_by: &'a AccountId,
+    pub fields: &'a [&'a str],
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action")]
+#[serde(rename_all = "snake_case")]
+pub enum OwnerUpdate<'a> {
+    Proposed {
+        owner_id: Option<&'a AccountId>,
+        proposed_owner_id: &'a AccountId,
+    },
+    Transferred {
+        previous_owner_id: Option<&'a AccountId>,
+        new_owner_id: Option<&'a AccountId>,
+    },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action")]
+#[serde(rename_all = "snake_case")]
+pub enum RoleUpdate<'a> {
+    Granted {
+        role: Role,
+        account_id: &'a AccountId,
+        by: &'a AccountId,
+    },
+    Revoked {
+        role: Role,
+        account_id: &'a AccountId,
+        by: &'a AccountId,
+    },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseUpdate<'a> {
+    pub paused: bool,
+    pub by: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FreezeUpdate<'a> {
+    pub account_id: &'a AccountId,
+    pub frozen: bool,
+    pub by: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferFeeUpdate<'a> {
+    pub transfer_fee: Option<&'a TransferFee>,
+    pub by: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeExemptionUpdate<'a> {
+    pub account_id: &'a AccountId,
+    pub exempt: bool,
+    pub by: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CirculationUpdate<'a> {
+    pub account_id: &'a AccountId,
+    pub excluded: bool,
+    pub by: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockupUpdate<'a> {
+    pub account_id: &'a AccountId,
+    pub amount: U128,
+    pub unlock_at: U64,
+    pub by: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action")]
+#[serde(rename_all = "snake_case")]
+pub enum UpgradeUpdate<'a> {
+    Proposed {
+        pending_upgrade: &'a PendingUpgrade,
+        by: &'a AccountId,
+    },
+    Cancelled {
+        pending_upgrade: &'a PendingUpgrade,
+        by: &'a AccountId,
+    },
+    DelayUpdated {
+        upgrade_delay: U64,
+        effective_at: U64,
+        by: &'a AccountId,
+    },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractUpgrade<'a> {
+    pub previous_code_hash: Option<Base58CryptoHash>,
+    pub code_hash: Base58CryptoHash,
+    pub by: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GovernanceUpdate<'a> {
+    pub previous_governance_id: Option<&'a AccountId>,
+    pub governance_id: Option<&'a AccountId>,
+    pub by: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegateChanged<'a> {
+    pub delegator: &'a AccountId,
+    pub from_delegate: Option<&'a AccountId>,
+    pub to_delegate: &'a AccountId,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegateVotesChanged<'a> {
+    pub delegate: &'a AccountId,
+    pub previous_votes: U128,
+    pub new_votes: U128,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AllowanceUpdate<'a> {
+    pub owner_id: &'a AccountId,
+    pub spender_id: &'a AccountId,
+    pub allowance: U128,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PermitKeyUpdate<'a> {
+    pub account_id: &'a AccountId,
+    pub public_key: &'a PublicKey,
+    pub registered: bool,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action")]
+#[serde(rename_all = "snake_case")]
+pub enum AirdropUpdate<'a> {
+    Created {
+        campaign_id: u64,
+        campaign: &'a AirdropCampaign,
+        by: &'a AccountId,
+    },
+    Claimed {
+        campaign_id: u64,
+        index: u64,
+        account_id: &'a AccountId,
+        amount: U128,
+    },
+    Swept {
+        campaign_id: u64,
+        amount: U128,
+        by: &'a AccountId,
+    },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action")]
+#[serde(rename_all = "snake_case")]
+pub enum VestingUpdate<'a> {
+    Created {
+        schedule: &'a VestingSchedule,
+    },
+    Claimed {
+        vesting_id: u64,
+        beneficiary: &'a AccountId,
+        amount: U128,
+    },
+    Revoked {
+        vesting_id: u64,
+        refund: U128,
+        by: &'a AccountId,
+    },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "action")]
+#[serde(rename_all = "snake_case")]
+pub enum MultisigUpdate<'a> {
+    Proposed {
+        id: u64,
+        by: &'a AccountId,
+    },
+    Approved {
+        id: u64,
+        by: &'a AccountId,
+    },
+    Revoked {
+        id: u64,
+        by: &'a AccountId,
+    },
+    Executed {
+        id: u64,
+        by: &'a AccountId,
+    },
+    Removed {
+        id: u64,
+        by: &'a AccountId,
+    },
+    SignersUpdated {
+        signers: &'a [AccountId],
+        threshold: u32,
+        proposal_lifetime: U64,
+        by: &'a AccountId,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a> {
+    standard: &'static str,
+    version: &'static str,
+    #[serde(flatten)]
+    event: &'a ContractEvent<'a>,
+}
+
+impl ContractEvent<'_> {
+    /// Logs the event to the host. This is required to ensure that the event is triggered
+    /// and to consume the event.
+    pub fn emit(self) {
+        let log = EventLog {
+            standard: EVENT_STANDARD,
+            version: EVENT_VERSION,
+            event: &self,
+        };
+        env::log_str(&format!(
+            "EVENT_JSON:{}",
+            serde_json::to_string(&log).unwrap_or_else(|_| env::abort())
+        ));
+    }
+}
@@ -0,0 +1,236 @@
+//! Optional fee on `ft_transfer`, `ft_transfer_call`, `ft_transfer_from` and `ft_batch_transfer`,
+//! that is routed to a fee receiver.
+//!
+//! The fee is a share of the transferred amount in basis points, capped per transfer. The receiver
+//! gets the amount without the fee, and both legs are logged as separate `ft_transfer` events.
+//! Transfers from or to exempt accounts or the fee receiver are free. If the receiver of
+//! `ft_transfer_call` returns tokens, the fee of the returned part is refunded to the sender.
+
+use crate::acl::paginate;
+use crate::event::{ContractEvent, FeeExemptionUpdate, TransferFeeUpdate};
+use crate::{Contract, ContractExt};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, log, near_bindgen, require, AccountId, Balance};
+use u256::U256;
+
+mod u256 {
+    #![allow(clippy::all)]
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+const MAX_BASIS_POINTS: u16 = 10_000;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TransferFee {
+    /// Share of the transferred amount in basis points, that is charged as fee.
+    pub basis_points: u16,
+    /// Maximum fee per transfer.
+    pub max_fee: U128,
+    /// Account, that receives the fees.
+    pub receiver_id: AccountId,
+}
+
+/// Fee, that has been charged for a transfer.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeLeg {
+    pub receiver_id: AccountId,
+    pub amount: U128,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sets the fee on all transfers or removes it, if `None` is given.
+    /// The fee receiver needs to be registered. Can only be called by the owner.
+    #[payable]
+    pub fn set_transfer_fee(&mut self, transfer_fee: Option<TransferFee>) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.internal_set_transfer_fee(transfer_fee);
+    }
+
+    pub fn ft_transfer_fee(&self) -> Option<TransferFee> {
+        self.transfer_fee.clone()
+    }
+
+    /// Exempts the given account from transfer fees, e.g. an exchange or an internal contract.
+    /// Transfers are free, if either the sender or the receiver is exempt.
+    /// Can only be called by the owner.
+    #[payable]
+    pub fn fee_exempt_add(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.internal_set_fee_exempt(account_id, true);
+    }
+
+    /// Removes the fee exemption of the given account. Can only be called by the owner.
+    #[payable]
+    pub fn fee_exempt_remove(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.internal_set_fee_exempt(account_id, false);
+    }
+
+    pub fn is_fee_exempt(&self, account_id: AccountId) -> bool {
+        self.fee_exempt.contains(&account_id)
+    }
+
+    /// Returns a page of all accounts, that are exempt from transfer fees.
+    pub fn fee_exempt_accounts(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        paginate(self.fee_exempt.as_vector().iter(), from_index, limit)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_set_transfer_fee(&mut self, transfer_fee: Option<TransferFee>) {
+        if let Some(transfer_fee) = &transfer_fee {
+            require!(
+                transfer_fee.basis_points > 0 && transfer_fee.basis_points < MAX_BASIS_POINTS,
+                "Basis points must be between 0 and 10000"
+            );
+            require!(transfer_fee.max_fee.0 > 0, "Max fee must be positive");
+            require!(
+                self.token.accounts.contains_key(&transfer_fee.receiver_id),
+                format!("The account {} is not registered", transfer_fee.receiver_id)
+            );
+        }
+        self.transfer_fee = transfer_fee;
+
+        ContractEvent::TransferFeeUpdate(TransferFeeUpdate {
+            transfer_fee: self.transfer_fee.as_ref(),
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+
+    pub(crate) fn internal_set_fee_exempt(&mut self, account_id: AccountId, exempt: bool) {
+        if exempt {
+            require!(
+                self.fee_exempt.insert(&account_id),
+                "Account is already exempt"
+            );
+        } else {
+            require!(self.fee_exempt.remove(&account_id), "Account is not exempt");
+        }
+        Self::emit_fee_exemption_update(&account_id, exempt);
+    }
+
+    /// Transfers tokens and charges the transfer fee, if any. The sender must be able to spend
+    /// the amount including the fee, and the transfer of the fee must be allowed as well.
+    /// Returns the amount, that the receiver got, and the charged fee.
+    pub(crate) fn internal_transfer_with_fee(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+        memo: Option<String>,
+    ) -> (Balance, Option<FeeLeg>) {
+        self.assert_spendable(sender_id, amount);
+        let (fee_receiver_id, fee) = match self.internal_fee(sender_id, receiver_id, amount) {
+            Some(fee) => fee,
+            None => {
+                self.internal_transfer(sender_id, receiver_id, amount, memo);
+                return (amount, None);
+            }
+        };
+        self.assert_transfer_allowed(sender_id, &fee_receiver_id);
+        let net_amount = amount - fee;
+        self.with_checkpoints(&[sender_id, receiver_id, &fee_receiver_id], |this| {
+            this.token
+                .internal_transfer(sender_id, receiver_id, net_amount, memo);
+            this.token.internal_transfer(
+                sender_id,
+                &fee_receiver_id,
+                fee,
+                Some("Transfer fee".to_string()),
+            );
+        });
+        let fee = FeeLeg {
+            receiver_id: fee_receiver_id,
+            amount: fee.into(),
+        };
+        (net_amount, Some(fee))
+    }
+
+    /// Refunds the fee of the part of `amount`, that has not been used by the receiver.
+    /// The refund is limited by the unreserved and unlocked balance of the fee receiver. It is
+    /// skipped, if the transfer from the fee receiver is not allowed, since the transfer has
+    /// already happened and resolving it must not fail.
+    pub(crate) fn internal_refund_fee(
+        &mut self,
+        sender_id: &AccountId,
+        fee: FeeLeg,
+        amount: Balance,
+        used_amount: Balance,
+    ) {
+        if used_amount >= amount || &fee.receiver_id == sender_id {
+            return;
+        }
+        if self.paused
+            || self.frozen_accounts.contains(&fee.receiver_id)
+            || self.frozen_accounts.contains(sender_id)
+        {
+            log!("Transfer fee of {} is not refunded", sender_id);
+            return;
+        }
+        let mut available = self.internal_spendable_balance(&fee.receiver_id);
+        if fee.receiver_id == Self::treasury_id() {
+            available = available.saturating_sub(self.treasury_reserved);
+        }
+        let refund = mul_div(fee.amount.0, amount - used_amount, amount).min(available);
+        if refund > 0 {
+            self.internal_transfer(
+                &fee.receiver_id,
+                sender_id,
+                refund,
+                Some("Transfer fee refund".to_string()),
+            );
+        }
+    }
+
+    /// Returns the fee receiver and the fee for the given transfer, if a fee is charged.
+    pub(crate) fn internal_fee(
+        &self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+    ) -> Option<(AccountId, Balance)> {
+        let transfer_fee = self.transfer_fee.as_ref()?;
+        if [sender_id, receiver_id].into_iter().any(|account_id| {
+            account_id == &transfer_fee.receiver_id || self.fee_exempt.contains(account_id)
+        }) {
+            return None;
+        }
+        let fee = mul_div(
+            amount,
+            transfer_fee.basis_points.into(),
+            MAX_BASIS_POINTS.into(),
+        )
+        .min(transfer_fee.max_fee.0);
+        (fee > 0).then(|| (transfer_fee.receiver_id.clone(), fee))
+    }
+
+    fn emit_fee_exemption_update(account_id: &AccountId, exempt: bool) {
+        ContractEvent::FeeExemptionUpdate(FeeExemptionUpdate {
+            account_id,
+            exempt,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+}
+
+/// Returns `a * b / c` rounded down without overflowing. The result must fit into a `u128`,
+/// which is the case for `b <= c`.
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    (U256::from(a) * U256::from(b) / U256::from(c)).as_u128()
+}
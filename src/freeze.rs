@@ -0,0 +1,59 @@
+use crate::acl::{paginate, Role};
+use crate::event::{ContractEvent, FreezeUpdate};
+use crate::{Contract, ContractExt};
+use near_sdk::json_types::U128;
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId};
+
+#[near_bindgen]
+impl Contract {
+    /// Freezes the given account, so that it can neither send nor receive tokens.
+    /// Can only be called by a freezer.
+    #[payable]
+    pub fn freeze_account(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_role(Role::Freezer);
+        require!(
+            self.frozen_accounts.insert(&account_id),
+            "Account is already frozen"
+        );
+        Self::emit_freeze_update(&account_id, true);
+    }
+
+    /// Unfreezes the given account. Can only be called by a freezer.
+    #[payable]
+    pub fn unfreeze_account(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_role(Role::Freezer);
+        require!(
+            self.frozen_accounts.remove(&account_id),
+            "Account is not frozen"
+        );
+        Self::emit_freeze_update(&account_id, false);
+    }
+
+    pub fn is_frozen(&self, account_id: AccountId) -> bool {
+        self.frozen_accounts.contains(&account_id)
+    }
+
+    /// Returns a page of all frozen accounts.
+    pub fn frozen_accounts(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+        paginate(self.frozen_accounts.as_vector().iter(), from_index, limit)
+    }
+}
+
+impl Contract {
+    pub(crate) fn assert_not_frozen(&self, account_id: &AccountId) {
+        if self.frozen_accounts.contains(account_id) {
+            env::panic_str(&format!("Account {} is frozen", account_id));
+        }
+    }
+
+    fn emit_freeze_update(account_id: &AccountId, frozen: bool) {
+        ContractEvent::FreezeUpdate(FreezeUpdate {
+            account_id,
+            frozen,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+}
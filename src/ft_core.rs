@@ -0,0 +1,123 @@
+//! NEP-141 core implementation. This replaces `impl_fungible_token_core!`, so that transfers
+//! can be intercepted by the contract, before they are forwarded to [`FungibleToken`].
+//!
+//! [`FungibleToken`]: near_contract_standards::fungible_token::FungibleToken
+
+use crate::fee::FeeLeg;
+use crate::{Contract, ContractExt};
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_contract_standards::fungible_token::receiver::ext_ft_receiver;
+use near_sdk::json_types::U128;
+use near_sdk::{
+    assert_one_yocto, env, log, near_bindgen, require, AccountId, Balance, Gas, PromiseOrValue,
+};
+
+/// Gas for `ft_resolve_transfer`, which records checkpoints and may refund a transfer fee.
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(20_000_000_000_000);
+/// Gas, that is kept for the rest of `ft_transfer_call` and for `ft_resolve_transfer`, when the
+/// remaining gas is passed to `ft_on_transfer`.
+const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas(25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER.0);
+
+#[near_bindgen]
+impl FungibleTokenCore for Contract {
+    #[payable]
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        assert_one_yocto();
+        let sender_id = env::predecessor_account_id();
+        self.assert_transfer_allowed(&sender_id, &receiver_id);
+        self.internal_transfer_with_fee(&sender_id, &receiver_id, amount.0, memo);
+    }
+
+    #[payable]
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        assert_one_yocto();
+        let sender_id = env::predecessor_account_id();
+        self.assert_transfer_allowed(&sender_id, &receiver_id);
+        let (amount, fee) =
+            self.internal_transfer_with_fee(&sender_id, &receiver_id, amount.0, memo);
+        let remaining_gas = env::prepaid_gas() - env::used_gas();
+        require!(
+            remaining_gas > GAS_FOR_FT_TRANSFER_CALL,
+            "More gas is required"
+        );
+
+        ext_ft_receiver::ext(receiver_id.clone())
+            .with_static_gas(remaining_gas - GAS_FOR_FT_TRANSFER_CALL)
+            .ft_on_transfer(sender_id.clone(), amount.into(), msg)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                    .ft_resolve_transfer(sender_id, receiver_id, amount.into(), fee),
+            )
+            .into()
+    }
+
+    fn ft_total_supply(&self) -> U128 {
+        self.token.ft_total_supply()
+    }
+
+    fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        self.token.ft_balance_of(account_id)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Resolves `ft_transfer_call` like `FungibleTokenResolver::ft_resolve_transfer` and
+    /// additionally refunds the fee of the returned tokens, if a transfer fee has been charged.
+    ///
+    /// Refunds are still resolved while the contract is paused or if any of the accounts
+    /// has been frozen in the meantime, because the tokens have already been transferred before.
+    /// Refunded tokens of a frozen sender stay frozen.
+    #[private]
+    pub fn ft_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        fee: Option<FeeLeg>,
+    ) -> U128 {
+        for account_id in [&sender_id, &receiver_id] {
+            if self.frozen_accounts.contains(account_id) {
+                log!("Resolving transfer of frozen account {}", account_id);
+            }
+        }
+        let used_amount = self.with_checkpoints(&[&sender_id, &receiver_id.clone()], |this| {
+            this.token
+                .internal_ft_resolve_transfer(&sender_id, receiver_id, amount)
+                .0
+        });
+        if let Some(fee) = fee {
+            self.internal_refund_fee(&sender_id, fee, amount.0, used_amount);
+        }
+        used_amount.into()
+    }
+}
+
+impl Contract {
+    /// Transfers tokens between two registered accounts and records the new balances.
+    pub(crate) fn internal_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+        memo: Option<String>,
+    ) {
+        self.with_checkpoints(&[sender_id, receiver_id], |this| {
+            this.token
+                .internal_transfer(sender_id, receiver_id, amount, memo)
+        });
+    }
+
+    pub(crate) fn assert_transfer_allowed(&self, sender_id: &AccountId, receiver_id: &AccountId) {
+        self.assert_not_paused();
+        self.assert_not_frozen(sender_id);
+        self.assert_not_frozen(receiver_id);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::event::{ContractEvent, GovernanceUpdate};
+use crate::{Contract, ContractExt};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId};
+
+#[near_bindgen]
+impl Contract {
+    /// Returns the DAO, that administers the contract, if configured.
+    pub fn governance_get(&self) -> Option<AccountId> {
+        self.governance_id.clone()
+    }
+
+    /// Hands the administration of the contract over to the given DAO, or back to the owner, if
+    /// `None` is given. While a DAO is configured, owner and role privileged methods can only be
+    /// called by the DAO, so this method can only be called by the owner until a DAO is
+    /// configured and only by the DAO afterwards.
+    #[payable]
+    pub fn set_governance(&mut self, governance_id: Option<AccountId>) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.internal_set_governance(governance_id);
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_set_governance(&mut self, governance_id: Option<AccountId>) {
+        require!(
+            self.governance_id != governance_id,
+            "Governance is already set to this account"
+        );
+
+        ContractEvent::GovernanceUpdate(GovernanceUpdate {
+            previous_governance_id: self.governance_id.as_ref(),
+            governance_id: governance_id.as_ref(),
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+
+        self.governance_id = governance_id;
+    }
+
+    pub(crate) fn assert_not_governed(&self) {
+        require!(
+            self.governance_id.is_none(),
+            "Privileged actions can only be executed by the governance"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Index of all accounts with a positive balance, that is updated on every balance change.
+//!
+//! Holders are kept in a set for pagination and in a tree ordered by balance for the top holders.
+//! Accounts, that have not changed their balance since the v1 state, can't be discovered on-chain,
+//! so the migration leaves the index incomplete and they are back-filled in batches via
+//! `holders_backfill`. The back-fill finishes, once the balances of all indexed holders add up to
+//! the total supply. Until then all holder queries fail.
+
+use crate::acl::paginate;
+use crate::{Contract, ContractExt};
+use near_sdk::json_types::U128;
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId, Balance};
+
+/// Maximum number of accounts, that can be back-filled in a single call.
+const MAX_BACKFILL: usize = 100;
+
+#[near_bindgen]
+impl Contract {
+    /// Returns a page of all accounts with a positive balance and their balances.
+    pub fn ft_holders(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<(AccountId, U128)> {
+        self.assert_holders_complete();
+        paginate(
+            self.holders.as_vector().iter().map(|account_id| {
+                let balance = self.token.accounts.get(&account_id).unwrap_or_default();
+                (account_id, balance.into())
+            }),
+            from_index,
+            limit,
+        )
+    }
+
+    /// Returns the number of accounts with a positive balance.
+    pub fn ft_holder_count(&self) -> u64 {
+        self.assert_holders_complete();
+        self.holders.len()
+    }
+
+    /// Returns whether the back-fill of the holders of the v1 state has finished. Until then
+    /// `ft_holders`, `ft_holder_count` and `ft_top_holders` fail.
+    pub fn ft_holders_complete(&self) -> bool {
+        self.holders_complete
+    }
+
+    /// Returns the accounts with the highest balances, ordered by balance descending.
+    /// Defaults to the top 10 holders and returns at most 100 holders.
+    pub fn ft_top_holders(&self, limit: Option<u64>) -> Vec<(AccountId, U128)> {
+        let limit = limit.unwrap_or(10);
+        require!(limit != 0, "Cannot provide limit of 0");
+        require!(limit <= 100, "Cannot provide limit above 100");
+        self.assert_holders_complete();
+        self.holder_ranking
+            .iter_rev()
+            .take(limit as usize)
+            .map(|((balance, account_id), _)| (account_id, balance.into()))
+            .collect()
+    }
+
+    /// Adds the given accounts to the holder index, if they have a positive balance and are not
+    /// indexed yet, and finishes the back-fill of the holders of the v1 state, once all of them
+    /// are indexed. Returns the number of added accounts.
+    ///
+    /// Can be called by anyone, since only actual holders are added. The storage of the added
+    /// accounts is charged to the storage balance of the caller. Requires exactly 1 yoctoNEAR
+    /// attached.
+    #[payable]
+    pub fn holders_backfill(&mut self, account_ids: Vec<AccountId>) -> u64 {
+        assert_one_yocto();
+        require!(
+            !self.holders_complete,
+            "The holder index is already complete"
+        );
+        require!(
+            account_ids.len() <= MAX_BACKFILL,
+            format!(
+                "Cannot back-fill more than {} accounts at once",
+                MAX_BACKFILL
+            )
+        );
+        let storage_usage = env::storage_usage();
+        let mut added = 0;
+        for account_id in account_ids {
+            if self.holders.contains(&account_id) {
+                continue;
+            }
+            let balance = self.token.accounts.get(&account_id).unwrap_or_default();
+            if balance > 0 {
+                self.internal_update_holder(&account_id, 0, balance);
+                added += 1;
+            }
+        }
+        self.internal_charge_storage(storage_usage);
+        self.holders_complete = self.holders_supply == self.token.total_supply;
+        added
+    }
+}
+
+impl Contract {
+    fn assert_holders_complete(&self) {
+        require!(
+            self.holders_complete,
+            "The holders of the v1 state have not all been back-filled yet"
+        );
+    }
+
+    pub(crate) fn internal_update_holder(
+        &mut self,
+        account_id: &AccountId,
+        previous: Balance,
+        balance: Balance,
+    ) {
+        if self
+            .holder_ranking
+            .remove(&(previous, account_id.clone()))
+            .is_some()
+        {
+            self.holders_supply -= previous;
+        }
+        if balance > 0 {
+            self.holders_supply += balance;
+            self.holders.insert(account_id);
+            self.holder_ranking
+                .insert(&(balance, account_id.clone()), &());
+        } else {
+            self.holders.remove(account_id);
+        }
+    }
+}
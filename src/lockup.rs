@@ -0,0 +1,191 @@
+//! Lockups, that keep a part of an account's balance non-transferable until a timestamp.
+//!
+//! Locked tokens still count towards `ft_balance_of`, but every transfer or burn from the account
+//! must leave at least the locked amount behind. Expired lockups are ignored and pruned, whenever
+//! a new lockup is added to the account.
+//!
+//! The sum of all lockups is kept for the circulating supply. Expired lockups are subtracted from
+//! it, when it is read, until they are released in the order of their unlock time by `ft_lock` or
+//! by anyone via `release_expired_lockups`, which keeps reading it cheap.
+
+use crate::acl::Role;
+use crate::allowance::refund_deposit;
+use crate::event::{ContractEvent, LockupUpdate};
+use crate::{Contract, ContractExt};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, require, AccountId, Balance};
+
+/// Maximum number of unlock times, whose expired lockups are released in a single call.
+const MAX_RELEASED_EXPIRIES: u64 = 100;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Lockup {
+    pub amount: U128,
+    /// Timestamp in nanoseconds, at which the tokens become transferable again.
+    pub unlock_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Locks `amount` tokens of the given account until `unlock_at`. Holders can lock their own
+    /// tokens, while locking tokens of other accounts requires the locker role. The account must
+    /// have enough tokens, that are not locked yet.
+    ///
+    /// Enough deposit for the storage of the lockup needs to be attached, at least 1 yoctoNEAR.
+    /// Any deposit, that is not needed, is refunded.
+    #[payable]
+    pub fn ft_lock(&mut self, account_id: AccountId, amount: U128, unlock_at: U64) {
+        require!(
+            env::attached_deposit() >= 1,
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        let caller_id = env::predecessor_account_id();
+        if caller_id != account_id {
+            self.assert_role(Role::Locker);
+        }
+        require!(amount.0 > 0, "The amount should be a positive number");
+        require!(
+            unlock_at.0 > env::block_timestamp(),
+            "Unlock time must be in the future"
+        );
+        require!(
+            self.internal_spendable_balance(&account_id) >= amount.0,
+            "Not enough unlocked tokens"
+        );
+        let storage_usage = env::storage_usage();
+
+        let now = env::block_timestamp();
+        let mut lockups = self.lockups.get(&account_id).unwrap_or_default();
+        lockups.retain(|lockup| lockup.unlock_at.0 > now);
+        lockups.push(Lockup { amount, unlock_at });
+        self.lockups.insert(&account_id, &lockups);
+        self.total_locked += amount.0;
+        let expiring = self.lockup_expiries.get(&unlock_at.0).unwrap_or_default();
+        self.lockup_expiries
+            .insert(&unlock_at.0, &(expiring + amount.0));
+        self.internal_release_expired_lockups(MAX_RELEASED_EXPIRIES);
+
+        ContractEvent::LockupUpdate(LockupUpdate {
+            account_id: &account_id,
+            amount,
+            unlock_at,
+            by: &caller_id,
+        })
+        .emit();
+        refund_deposit(storage_usage);
+    }
+
+    /// Removes expired lockups from the sum of all lockups, so that the circulating supply
+    /// doesn't need to skip them. Can be called by anyone and returns the number of released
+    /// unlock times.
+    pub fn release_expired_lockups(&mut self) -> u64 {
+        self.internal_release_expired_lockups(MAX_RELEASED_EXPIRIES)
+    }
+
+    /// Returns the amount of tokens of the account, that are currently locked.
+    pub fn ft_locked_balance_of(&self, account_id: AccountId) -> U128 {
+        self.internal_locked_balance(&account_id).into()
+    }
+
+    /// Returns the amount of tokens of the account, that can currently be transferred.
+    pub fn ft_spendable_balance_of(&self, account_id: AccountId) -> U128 {
+        self.internal_spendable_balance(&account_id).into()
+    }
+
+    /// Returns all lockups of the account, that have not expired yet.
+    pub fn ft_lockups_of(&self, account_id: AccountId) -> Vec<Lockup> {
+        let now = env::block_timestamp();
+        self.lockups
+            .get(&account_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|lockup| lockup.unlock_at.0 > now)
+            .collect()
+    }
+}
+
+impl Contract {
+    /// Releases the expired lockups of up to `limit` unlock times, the earliest first.
+    fn internal_release_expired_lockups(&mut self, limit: u64) -> u64 {
+        let now = env::block_timestamp();
+        let mut released = 0;
+        while released < limit {
+            match self.lockup_expiries.min() {
+                Some(unlock_at) if unlock_at <= now => {
+                    let amount = self.lockup_expiries.remove(&unlock_at).unwrap();
+                    self.total_locked -= amount;
+                    released += 1;
+                }
+                _ => break,
+            }
+        }
+        released
+    }
+
+    /// Removes all lockups of a closed account. Its lockups, that have not expired yet, won't be
+    /// released anymore, so they are subtracted right away.
+    pub(crate) fn internal_remove_lockups(&mut self, account_id: &AccountId) {
+        let now = env::block_timestamp();
+        let lockups = self.lockups.remove(account_id).unwrap_or_default();
+        for lockup in lockups.iter().filter(|lockup| lockup.unlock_at.0 > now) {
+            let unlock_at = lockup.unlock_at.0;
+            let expiring = self.lockup_expiries.get(&unlock_at).unwrap() - lockup.amount.0;
+            if expiring > 0 {
+                self.lockup_expiries.insert(&unlock_at, &expiring);
+            } else {
+                self.lockup_expiries.remove(&unlock_at);
+            }
+            self.total_locked -= lockup.amount.0;
+        }
+    }
+
+    /// Returns the sum of all lockups, that have not expired yet.
+    pub(crate) fn internal_total_locked(&self) -> Balance {
+        let now = env::block_timestamp();
+        let expired: Balance = self
+            .lockup_expiries
+            .iter()
+            .take_while(|(unlock_at, _)| *unlock_at <= now)
+            .map(|(_, amount)| amount)
+            .sum();
+        self.total_locked - expired
+    }
+
+    pub(crate) fn internal_locked_balance(&self, account_id: &AccountId) -> Balance {
+        let now = env::block_timestamp();
+        self.lockups
+            .get(account_id)
+            .unwrap_or_default()
+            .iter()
+            .filter(|lockup| lockup.unlock_at.0 > now)
+            .map(|lockup| lockup.amount.0)
+            .sum()
+    }
+
+    pub(crate) fn internal_spendable_balance(&self, account_id: &AccountId) -> Balance {
+        self.token
+            .accounts
+            .get(account_id)
+            .unwrap_or_default()
+            .saturating_sub(self.internal_locked_balance(account_id))
+    }
+
+    /// Asserts, that the account can spend the given amount without touching locked tokens.
+    /// Accounts without locked tokens are left to the balance check of the withdrawal.
+    pub(crate) fn assert_spendable(&self, account_id: &AccountId, amount: Balance) {
+        let locked = self.internal_locked_balance(account_id);
+        if locked == 0 {
+            return;
+        }
+        let balance = self.token.accounts.get(account_id).unwrap_or_default();
+        if balance.saturating_sub(locked) < amount {
+            env::panic_str(&format!(
+                "Account {} does not have enough unlocked tokens",
+                account_id
+            ));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Merkle trees of airdrop claims, that are verified by `airdrop_claim`.
+//!
+//! Every claim is a leaf, which is the hash of its index in the tree, the account and the
+//! amount. Leaves and inner nodes are hashed with different prefixes, so that an inner node
+//! can't be passed off as a leaf. If a layer has an odd number of nodes, the last node is paired
+//! with itself. The index of a leaf determines, whether the sibling at each layer is on the left
+//! or on the right, and its bit in the claim bitmap of the campaign.
+//!
+//! [`MerkleTree`] builds the tree and proofs off-chain with the same hashing, that the contract
+//! uses for verification.
+
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, AccountId, Balance, CryptoHash};
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// Proof, that a leaf with the given index is part of the tree. The path contains the sibling
+/// of every layer from the leaves up to the root.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MerkleProof {
+    pub index: u64,
+    pub path: Vec<Base58CryptoHash>,
+}
+
+/// Hashes the Borsh serialized `(index, account_id, amount)` with the leaf prefix.
+pub fn leaf_hash(index: u64, account_id: &AccountId, amount: Balance) -> CryptoHash {
+    let mut data = vec![LEAF_PREFIX];
+    BorshSerialize::serialize(&(index, account_id, amount), &mut data).unwrap();
+    env::sha256_array(&data)
+}
+
+/// Hashes the concatenation of both children with the node prefix.
+pub fn node_hash(left: &CryptoHash, right: &CryptoHash) -> CryptoHash {
+    let mut data = Vec::with_capacity(1 + 2 * left.len());
+    data.push(NODE_PREFIX);
+    data.extend_from_slice(left);
+    data.extend_from_slice(right);
+    env::sha256_array(&data)
+}
+
+/// Returns whether the proof leads from the leaf to the root.
+pub fn verify(root: &CryptoHash, leaf: CryptoHash, proof: &MerkleProof) -> bool {
+    let mut hash = leaf;
+    let mut index = proof.index;
+    for sibling in &proof.path {
+        let sibling = CryptoHash::from(*sibling);
+        hash = if index & 1 == 0 {
+            node_hash(&hash, &sibling)
+        } else {
+            node_hash(&sibling, &hash)
+        };
+        index /= 2;
+    }
+    index == 0 && &hash == root
+}
+
+/// Merkle tree of airdrop claims.
+pub struct MerkleTree {
+    layers: Vec<Vec<CryptoHash>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree of the given claims. The index of a claim is its position in the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if no claims are given.
+    pub fn new(claims: &[(AccountId, Balance)]) -> Self {
+        assert!(!claims.is_empty(), "At least one claim is required");
+        let mut layers = vec![claims
+            .iter()
+            .enumerate()
+            .map(|(index, (account_id, amount))| leaf_hash(index as u64, account_id, *amount))
+            .collect::<Vec<_>>()];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| node_hash(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+                .collect();
+            layers.push(layer);
+        }
+        Self { layers }
+    }
+
+    pub fn root(&self) -> CryptoHash {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// Returns the proof of the claim with the given index, if it exists.
+    pub fn proof(&self, index: u64) -> Option<MerkleProof> {
+        if index as usize >= self.layers[0].len() {
+            return None;
+        }
+        let mut position = index as usize;
+        let path = self.layers[..self.layers.len() - 1]
+            .iter()
+            .map(|layer| {
+                let sibling = layer.get(position ^ 1).unwrap_or(&layer[position]);
+                position /= 2;
+                Base58CryptoHash::from(*sibling)
+            })
+            .collect();
+        Some(MerkleProof { index, path })
+    }
+}
@@ -0,0 +1,81 @@
+use crate::acl::Role;
+use crate::event::{ContractEvent, MetadataUpdate};
+use crate::{Contract, ContractExt, DATA_IMAGE_SVG_GT_ICON};
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::{assert_one_yocto, env, near_bindgen, require};
+
+/// Maximum length of the icon data URI in bytes.
+/// Metadata is returned by `ft_metadata` on every wallet load, so it should stay small.
+pub const MAX_ICON_LENGTH: usize = 20 * 1024;
+
+#[near_bindgen]
+impl Contract {
+    /// Replaces the fungible token metadata. The `spec` and `decimals` can't be changed, because
+    /// wallets would silently rescale every balance.
+    #[payable]
+    pub fn set_metadata(&mut self, metadata: FungibleTokenMetadata) {
+        assert_one_yocto();
+        self.assert_role(Role::MetadataAdmin);
+        self.internal_set_metadata(metadata, &["metadata"]);
+    }
+
+    /// Updates the icon of the token. If no icon is given, the bundled WALC icon will be used.
+    #[payable]
+    pub fn set_icon(&mut self, icon: Option<String>) {
+        assert_one_yocto();
+        self.assert_role(Role::MetadataAdmin);
+        let mut metadata = self.metadata.get().unwrap();
+        metadata.icon = Some(icon.unwrap_or_else(|| DATA_IMAGE_SVG_GT_ICON.to_string()));
+        self.internal_set_metadata(metadata, &["icon"]);
+    }
+
+    /// Updates the reference and its hash. Both need to be either set or unset.
+    #[payable]
+    pub fn set_reference(
+        &mut self,
+        reference: Option<String>,
+        reference_hash: Option<Base64VecU8>,
+    ) {
+        assert_one_yocto();
+        self.assert_role(Role::MetadataAdmin);
+        let mut metadata = self.metadata.get().unwrap();
+        metadata.reference = reference;
+        metadata.reference_hash = reference_hash;
+        self.internal_set_metadata(metadata, &["reference", "reference_hash"]);
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_set_metadata(
+        &mut self,
+        metadata: FungibleTokenMetadata,
+        fields: &[&str],
+    ) {
+        assert_valid_metadata(&metadata);
+        let current = self.metadata.get().unwrap();
+        require!(metadata.spec == current.spec, "Spec cannot be changed");
+        require!(
+            metadata.decimals == current.decimals,
+            "Decimals cannot be changed"
+        );
+        self.metadata.set(&metadata);
+
+        ContractEvent::MetadataUpdate(MetadataUpdate {
+            updated_by: &env::predecessor_account_id(),
+            fields,
+        })
+        .emit();
+    }
+}
+
+pub(crate) fn assert_valid_metadata(metadata: &FungibleTokenMetadata) {
+    metadata.assert_valid();
+    if let Some(icon) = &metadata.icon {
+        require!(
+            icon.len() <= MAX_ICON_LENGTH,
+            format!("Icon must not exceed {} bytes", MAX_ICON_LENGTH)
+        );
+        require!(icon.starts_with("data:"), "Icon must be a data URI");
+    }
+}
@@ -0,0 +1,70 @@
+use crate::acl::Role;
+use crate::{Contract, ContractExt};
+use near_contract_standards::fungible_token::events::FtMint;
+use near_contract_standards::storage_management::StorageManagement;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId, Balance, Promise};
+
+#[near_bindgen]
+impl Contract {
+    /// Mints new tokens to the given account. Can only be called by a minter.
+    ///
+    /// If the receiver is not yet registered, enough deposit for the storage registration needs
+    /// to be attached, otherwise at least 1 yoctoNEAR. Any deposit, that is not needed for the
+    /// registration, is refunded.
+    #[payable]
+    pub fn ft_mint(&mut self, account_id: AccountId, amount: U128, memo: Option<String>) {
+        require!(
+            env::attached_deposit() >= 1,
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        self.assert_role(Role::Minter);
+        let amount: Balance = amount.into();
+        require!(amount > 0, "The amount should be a positive number");
+
+        if !self.token.accounts.contains_key(&account_id) {
+            self.token
+                .storage_deposit(Some(account_id.clone()), Some(true));
+        } else if env::attached_deposit() > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(env::attached_deposit());
+        }
+
+        self.internal_mint(&account_id, amount, memo.as_deref());
+    }
+
+    /// Returns the maximum supply of the token, if there is any.
+    pub fn ft_max_supply(&self) -> Option<U128> {
+        self.max_supply.map(U128)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_mint(
+        &mut self,
+        account_id: &AccountId,
+        amount: Balance,
+        memo: Option<&str>,
+    ) {
+        self.assert_not_paused();
+        if let Some(max_supply) = self.max_supply {
+            require!(
+                self.token
+                    .total_supply
+                    .checked_add(amount)
+                    .unwrap_or(Balance::MAX)
+                    <= max_supply,
+                "Max supply exceeded"
+            );
+        }
+        self.with_checkpoints(&[account_id], |this| {
+            this.token.internal_deposit(account_id, amount)
+        });
+
+        FtMint {
+            owner_id: account_id,
+            amount: &U128(amount),
+            memo,
+        }
+        .emit();
+    }
+}
@@ -0,0 +1,348 @@
+use crate::acl::paginate;
+use crate::event::{ContractEvent, MultisigUpdate};
+use crate::fee::TransferFee;
+use crate::{Contract, ContractExt, StorageKey};
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId};
+
+/// Default time in nanoseconds, after which a proposal expires: 7 days.
+pub const DEFAULT_PROPOSAL_LIFETIME: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+/// M-of-N signer set, that can execute privileged actions without relying on a single key.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Multisig {
+    signers: UnorderedSet<AccountId>,
+    threshold: u32,
+    proposal_lifetime: u64,
+    proposals: UnorderedMap<u64, MultisigProposal>,
+    next_proposal_id: u64,
+}
+
+impl Multisig {
+    pub fn new() -> Self {
+        Self {
+            signers: UnorderedSet::new(StorageKey::MultisigSigners.try_to_vec().unwrap()),
+            threshold: 0,
+            proposal_lifetime: DEFAULT_PROPOSAL_LIFETIME,
+            proposals: UnorderedMap::new(StorageKey::MultisigProposals.try_to_vec().unwrap()),
+            next_proposal_id: 0,
+        }
+    }
+
+    pub fn is_set_up(&self) -> bool {
+        !self.signers.is_empty()
+    }
+}
+
+/// Privileged action, that is executed once enough signers approved it.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+pub enum MultisigAction {
+    Mint {
+        account_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    },
+    Pause,
+    Unpause,
+    SetMetadata {
+        metadata: FungibleTokenMetadata,
+    },
+    /// Proposes a timelocked upgrade, which still has to be executed via `execute_upgrade`.
+    Upgrade {
+        code_hash: Base58CryptoHash,
+        eta: U64,
+    },
+    SetMultisig {
+        signers: Vec<AccountId>,
+        threshold: u32,
+        proposal_lifetime: U64,
+    },
+    SetUpgradeDelay {
+        upgrade_delay: U64,
+    },
+    SetGovernance {
+        governance_id: Option<AccountId>,
+    },
+    ProposeOwner {
+        new_owner: AccountId,
+    },
+    CreateAirdrop {
+        merkle_root: Base58CryptoHash,
+        amount: U128,
+        deadline: U64,
+    },
+    SweepAirdrop {
+        campaign_id: u64,
+    },
+    SetTransferFee {
+        transfer_fee: Option<TransferFee>,
+    },
+    SetFeeExempt {
+        account_id: AccountId,
+        exempt: bool,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MultisigProposal {
+    pub id: u64,
+    pub action: MultisigAction,
+    pub proposer: AccountId,
+    pub approvals: Vec<AccountId>,
+    /// Timestamp in nanoseconds, after which the proposal can no longer be approved or executed.
+    pub expires_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Sets up the initial signer set. Can only be called by the owner and only as long as
+    /// there are no signers. Afterwards the signer set can only be changed via a
+    /// `set_multisig` action, the owner no longer holds every role implicitly and owner
+    /// privileged methods can only be executed via multisig actions.
+    #[payable]
+    pub fn multisig_setup(
+        &mut self,
+        signers: Vec<AccountId>,
+        threshold: u32,
+        proposal_lifetime: Option<U64>,
+    ) {
+        assert_one_yocto();
+        self.assert_owner();
+        require!(
+            !self.multisig.is_set_up(),
+            "Multisig has already been set up"
+        );
+        self.internal_set_multisig(
+            signers,
+            threshold,
+            proposal_lifetime
+                .map(u64::from)
+                .unwrap_or(DEFAULT_PROPOSAL_LIFETIME),
+        );
+    }
+
+    /// Proposes a privileged action, which is approved by the proposer.
+    /// Returns the ID of the proposal. Can only be called by a signer.
+    #[payable]
+    pub fn propose_action(&mut self, action: MultisigAction) -> u64 {
+        assert_one_yocto();
+        let signer_id = self.assert_signer();
+        let id = self.multisig.next_proposal_id;
+        self.multisig.next_proposal_id += 1;
+
+        let proposal = MultisigProposal {
+            id,
+            action,
+            proposer: signer_id.clone(),
+            approvals: vec![signer_id.clone()],
+            expires_at: (env::block_timestamp() + self.multisig.proposal_lifetime).into(),
+        };
+        self.multisig.proposals.insert(&id, &proposal);
+
+        ContractEvent::MultisigUpdate(MultisigUpdate::Proposed { id, by: &signer_id }).emit();
+        id
+    }
+
+    /// Approves a proposal. Can only be called by a signer.
+    #[payable]
+    pub fn approve_action(&mut self, id: u64) {
+        assert_one_yocto();
+        let signer_id = self.assert_signer();
+        let mut proposal = self.internal_unexpired_proposal(id);
+        require!(
+            !proposal.approvals.contains(&signer_id),
+            "Proposal has already been approved"
+        );
+        proposal.approvals.push(signer_id.clone());
+        self.multisig.proposals.insert(&id, &proposal);
+
+        ContractEvent::MultisigUpdate(MultisigUpdate::Approved { id, by: &signer_id }).emit();
+    }
+
+    /// Revokes the approval of a proposal. The proposal is removed,
+    /// once there are no approvals left. Can only be called by a signer.
+    #[payable]
+    pub fn revoke_approval(&mut self, id: u64) {
+        assert_one_yocto();
+        let signer_id = self.assert_signer();
+        let mut proposal = self.internal_proposal(id);
+        let len = proposal.approvals.len();
+        proposal
+            .approvals
+            .retain(|account_id| account_id != &signer_id);
+        require!(
+            proposal.approvals.len() < len,
+            "Proposal has not been approved"
+        );
+
+        if proposal.approvals.is_empty() {
+            self.multisig.proposals.remove(&id);
+        } else {
+            self.multisig.proposals.insert(&id, &proposal);
+        }
+
+        ContractEvent::MultisigUpdate(MultisigUpdate::Revoked { id, by: &signer_id }).emit();
+    }
+
+    /// Executes a proposal, that has been approved by at least `threshold` current signers.
+    /// Can only be called by a signer and not while a governance DAO is configured.
+    #[payable]
+    pub fn execute_action(&mut self, id: u64) {
+        assert_one_yocto();
+        let signer_id = self.assert_signer();
+        self.assert_not_governed();
+        let proposal = self.internal_unexpired_proposal(id);
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|account_id| self.multisig.signers.contains(account_id))
+            .count();
+        require!(
+            approvals >= self.multisig.threshold as usize,
+            "Proposal has not been approved by enough signers"
+        );
+        self.multisig.proposals.remove(&id);
+
+        ContractEvent::MultisigUpdate(MultisigUpdate::Executed { id, by: &signer_id }).emit();
+
+        match proposal.action {
+            MultisigAction::Mint {
+                account_id,
+                amount,
+                memo,
+            } => self.internal_mint(&account_id, amount.into(), memo.as_deref()),
+            MultisigAction::Pause => self.internal_set_paused(true),
+            MultisigAction::Unpause => self.internal_set_paused(false),
+            MultisigAction::SetMetadata { metadata } => {
+                self.internal_set_metadata(metadata, &["metadata"])
+            }
+            MultisigAction::Upgrade { code_hash, eta } => {
+                self.internal_propose_upgrade(code_hash, eta)
+            }
+            MultisigAction::SetMultisig {
+                signers,
+                threshold,
+                proposal_lifetime,
+            } => self.internal_set_multisig(signers, threshold, proposal_lifetime.into()),
+            MultisigAction::SetUpgradeDelay { upgrade_delay } => {
+                self.internal_set_upgrade_delay(upgrade_delay)
+            }
+            MultisigAction::SetGovernance { governance_id } => {
+                self.internal_set_governance(governance_id)
+            }
+            MultisigAction::ProposeOwner { new_owner } => self.internal_owner_propose(new_owner),
+            MultisigAction::CreateAirdrop {
+                merkle_root,
+                amount,
+                deadline,
+            } => {
+                self.internal_airdrop_create(merkle_root, amount, deadline);
+            }
+            MultisigAction::SweepAirdrop { campaign_id } => {
+                self.internal_airdrop_sweep(campaign_id)
+            }
+            MultisigAction::SetTransferFee { transfer_fee } => {
+                self.internal_set_transfer_fee(transfer_fee)
+            }
+            MultisigAction::SetFeeExempt { account_id, exempt } => {
+                self.internal_set_fee_exempt(account_id, exempt)
+            }
+        }
+    }
+
+    /// Removes an expired proposal. Can only be called by a signer.
+    #[payable]
+    pub fn remove_expired_action(&mut self, id: u64) {
+        assert_one_yocto();
+        let signer_id = self.assert_signer();
+        let proposal = self.internal_proposal(id);
+        require!(
+            env::block_timestamp() >= proposal.expires_at.0,
+            "Proposal has not yet expired"
+        );
+        self.multisig.proposals.remove(&id);
+
+        ContractEvent::MultisigUpdate(MultisigUpdate::Removed { id, by: &signer_id }).emit();
+    }
+
+    pub fn multisig_signers(&self) -> Vec<AccountId> {
+        self.multisig.signers.to_vec()
+    }
+
+    pub fn multisig_threshold(&self) -> u32 {
+        self.multisig.threshold
+    }
+
+    pub fn multisig_proposal(&self, id: u64) -> Option<MultisigProposal> {
+        self.multisig.proposals.get(&id)
+    }
+
+    /// Returns a page of all proposals, including the expired ones.
+    pub fn multisig_proposals(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<MultisigProposal> {
+        paginate(self.multisig.proposals.values(), from_index, limit)
+    }
+}
+
+impl Contract {
+    fn assert_signer(&self) -> AccountId {
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.multisig.signers.contains(&account_id),
+            "Only signers can call this method"
+        );
+        account_id
+    }
+
+    fn internal_proposal(&self, id: u64) -> MultisigProposal {
+        self.multisig
+            .proposals
+            .get(&id)
+            .unwrap_or_else(|| env::panic_str("Proposal does not exist"))
+    }
+
+    fn internal_unexpired_proposal(&self, id: u64) -> MultisigProposal {
+        let proposal = self.internal_proposal(id);
+        require!(
+            env::block_timestamp() < proposal.expires_at.0,
+            "Proposal has expired"
+        );
+        proposal
+    }
+
+    fn internal_set_multisig(
+        &mut self,
+        signers: Vec<AccountId>,
+        threshold: u32,
+        proposal_lifetime: u64,
+    ) {
+        self.multisig.signers.clear();
+        self.multisig.signers.extend(signers);
+        require!(
+            threshold > 0 && threshold as u64 <= self.multisig.signers.len(),
+            "Threshold must be between 1 and the number of signers"
+        );
+        require!(proposal_lifetime > 0, "Proposal lifetime must be positive");
+        self.multisig.threshold = threshold;
+        self.multisig.proposal_lifetime = proposal_lifetime;
+
+        ContractEvent::MultisigUpdate(MultisigUpdate::SignersUpdated {
+            signers: &self.multisig.signers.to_vec(),
+            threshold,
+            proposal_lifetime: proposal_lifetime.into(),
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+}
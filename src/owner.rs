@@ -0,0 +1,99 @@
+use crate::event::{ContractEvent, OwnerUpdate};
+use crate::{Contract, ContractExt};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId};
+
+#[near_bindgen]
+impl Contract {
+    /// Returns the current owner of the contract, if the ownership has not been renounced.
+    pub fn owner_get(&self) -> Option<AccountId> {
+        self.owner_id.clone()
+    }
+
+    /// Returns the account, that has been proposed as the new owner, but did not yet accept.
+    pub fn owner_get_proposed(&self) -> Option<AccountId> {
+        self.proposed_owner_id.clone()
+    }
+
+    /// Proposes a new owner. The ownership is only transferred, once the proposed account
+    /// calls `owner_accept`. A pending proposal is replaced by calling this method again.
+    #[payable]
+    pub fn owner_propose(&mut self, new_owner: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.internal_owner_propose(new_owner);
+    }
+
+    /// Accepts the ownership proposal. Must be called by the proposed owner.
+    #[payable]
+    pub fn owner_accept(&mut self) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.proposed_owner_id.as_ref() == Some(&account_id),
+            "Only the proposed owner can accept the ownership"
+        );
+
+        ContractEvent::OwnerUpdate(OwnerUpdate::Transferred {
+            previous_owner_id: self.owner_id.as_ref(),
+            new_owner_id: Some(&account_id),
+        })
+        .emit();
+
+        self.owner_id = Some(account_id);
+        self.proposed_owner_id = None;
+    }
+
+    /// Renounces the ownership. Afterwards no owner privileged method can be called anymore.
+    #[payable]
+    pub fn owner_renounce(&mut self) {
+        assert_one_yocto();
+        self.assert_owner();
+
+        ContractEvent::OwnerUpdate(OwnerUpdate::Transferred {
+            previous_owner_id: self.owner_id.as_ref(),
+            new_owner_id: None,
+        })
+        .emit();
+
+        self.owner_id = None;
+        self.proposed_owner_id = None;
+    }
+}
+
+impl Contract {
+    /// Asserts, that the caller is the owner or, if configured, the governance DAO, which
+    /// replaces the owner. Once the multisig has been set up, the owner can't act alone anymore
+    /// and owner privileged actions need to be executed via multisig actions.
+    pub(crate) fn assert_owner(&self) {
+        if let Some(governance_id) = &self.governance_id {
+            require!(
+                governance_id == &env::predecessor_account_id(),
+                "Only the governance can call this method"
+            );
+            return;
+        }
+        require!(
+            self.owner_id.as_ref() == Some(&env::predecessor_account_id()),
+            "Only the owner can call this method"
+        );
+        require!(
+            !self.multisig.is_set_up(),
+            "Owner actions require the approval of the multisig signers"
+        );
+    }
+
+    pub(crate) fn internal_owner_propose(&mut self, new_owner: AccountId) {
+        require!(
+            self.owner_id.as_ref() != Some(&new_owner),
+            "Account is already the owner"
+        );
+
+        ContractEvent::OwnerUpdate(OwnerUpdate::Proposed {
+            owner_id: self.owner_id.as_ref(),
+            proposed_owner_id: &new_owner,
+        })
+        .emit();
+
+        self.proposed_owner_id = Some(new_owner);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::acl::Role;
+use crate::event::{ContractEvent, PauseUpdate};
+use crate::{Contract, ContractExt};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require};
+
+#[near_bindgen]
+impl Contract {
+    /// Halts all transfers and minting. Views and storage management keep working.
+    /// Can only be called by a pauser.
+    #[payable]
+    pub fn pause(&mut self) {
+        assert_one_yocto();
+        self.assert_role(Role::Pauser);
+        self.internal_set_paused(true);
+    }
+
+    /// Resumes transfers and minting. Can only be called by a pauser.
+    #[payable]
+    pub fn unpause(&mut self) {
+        assert_one_yocto();
+        self.assert_role(Role::Pauser);
+        self.internal_set_paused(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+impl Contract {
+    pub(crate) fn assert_not_paused(&self) {
+        require!(!self.paused, "Contract is paused");
+    }
+
+    pub(crate) fn internal_set_paused(&mut self, paused: bool) {
+        if paused {
+            require!(!self.paused, "Contract is already paused");
+        } else {
+            require!(self.paused, "Contract is not paused");
+        }
+        self.paused = paused;
+
+        ContractEvent::PauseUpdate(PauseUpdate {
+            paused,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+}
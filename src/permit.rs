@@ -0,0 +1,232 @@
+//! Signed permits, that set an allowance without the holder sending a transaction.
+//!
+//! A holder registers ed25519 public keys for their account via `permit_add_key`, proving the
+//! possession of the key with a signature over a [`PermitKeyMessage`]. Afterwards they
+//! can sign a [`PermitMessage`] with one of these keys and anyone (e.g. a relayer) can submit it
+//! via `ft_permit`, which sets the allowance of the spender. The spender then transfers the
+//! tokens via `ft_transfer_from`. Every permit uses the next nonce of the holder, so that it
+//! can't be replayed.
+
+use crate::allowance::refund_deposit;
+use crate::event::{ContractEvent, PermitKeyUpdate};
+use crate::{Contract, ContractExt, StorageKey};
+use near_sdk::borsh::{self, BorshSerialize};
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::{
+    assert_one_yocto, env, near_bindgen, require, AccountId, Balance, CurveType, Promise, PublicKey,
+};
+
+/// Payload, that is signed by the holder. The signature is created over the Borsh serialized
+/// message.
+#[derive(BorshSerialize)]
+pub struct PermitMessage {
+    pub contract_id: AccountId,
+    pub spender_id: AccountId,
+    pub amount: Balance,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+/// Payload, that is signed by the key, that is registered via `permit_add_key`. Otherwise
+/// anyone could register the public key of another holder for themselves, before the holder
+/// does. The signature is created over the Borsh serialized message.
+#[derive(BorshSerialize)]
+pub struct PermitKeyMessage {
+    pub contract_id: AccountId,
+    pub account_id: AccountId,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Approves the spender to transfer up to `amount` tokens of the account, that registered the
+    /// public key. Fails, if the spender already has an allowance, like `ft_approve`. The signature must be created by this key over the [`PermitMessage`], with
+    /// `nonce` being the current permit nonce of the holder and `deadline` being the timestamp in
+    /// nanoseconds, until which the permit can be used.
+    ///
+    /// Can be called by anyone. Enough deposit for the storage of the allowance needs to be
+    /// attached, at least 1 yoctoNEAR. Any deposit, that is not needed, is refunded. The caller
+    /// gets the storage deposit refunded, once the allowance is removed.
+    #[payable]
+    pub fn ft_permit(
+        &mut self,
+        public_key: PublicKey,
+        spender_id: AccountId,
+        amount: U128,
+        nonce: U64,
+        deadline: U64,
+        signature: Base64VecU8,
+    ) {
+        require!(
+            env::block_timestamp() <= deadline.0,
+            "Permit deadline has passed"
+        );
+        let owner_id = self
+            .permit_keys
+            .get(&public_key)
+            .unwrap_or_else(|| env::panic_str("Public key is not registered"));
+        let expected_nonce = self.permit_nonces.get(&owner_id).unwrap_or_default();
+        require!(nonce.0 == expected_nonce, "Invalid permit nonce");
+
+        let message = PermitMessage {
+            contract_id: env::current_account_id(),
+            spender_id,
+            amount: amount.into(),
+            nonce: nonce.0,
+            deadline: deadline.0,
+        };
+        require!(
+            ed25519_verify(
+                &signature.0,
+                &message.try_to_vec().unwrap(),
+                &public_key.as_bytes()[1..]
+            ),
+            "Invalid permit signature"
+        );
+
+        require!(message.amount > 0, "The amount should be a positive number");
+        require!(
+            self.internal_allowance(&owner_id, &message.spender_id) == 0,
+            "Allowance already exists, use ft_increase_allowance or ft_decrease_allowance"
+        );
+
+        self.permit_nonces.insert(&owner_id, &(expected_nonce + 1));
+        self.internal_set_allowance_paid(&owner_id, &message.spender_id, message.amount);
+    }
+
+    /// Returns the nonce, that the next permit of the account needs to be signed with.
+    pub fn ft_permit_nonce(&self, account_id: AccountId) -> U64 {
+        self.permit_nonces
+            .get(&account_id)
+            .unwrap_or_default()
+            .into()
+    }
+
+    /// Registers an ed25519 public key, that can sign permits for the caller. A key can only be
+    /// registered for a single account. The signature must be created by this key over the
+    /// [`PermitKeyMessage`] with the caller as `account_id`.
+    ///
+    /// Enough deposit for the storage of the key needs to be attached, at least 1 yoctoNEAR.
+    /// Any deposit, that is not needed, is refunded.
+    #[payable]
+    pub fn permit_add_key(&mut self, public_key: PublicKey, signature: Base64VecU8) {
+        require!(
+            env::attached_deposit() >= 1,
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        require!(
+            public_key.curve_type() == CurveType::ED25519,
+            "Only ed25519 keys are supported"
+        );
+        require!(
+            !self.permit_keys.contains_key(&public_key),
+            "Public key is already registered"
+        );
+        let account_id = env::predecessor_account_id();
+        let message = PermitKeyMessage {
+            contract_id: env::current_account_id(),
+            account_id,
+        };
+        require!(
+            ed25519_verify(
+                &signature.0,
+                &message.try_to_vec().unwrap(),
+                &public_key.as_bytes()[1..]
+            ),
+            "Invalid key signature"
+        );
+        let account_id = message.account_id;
+        let storage_usage = env::storage_usage();
+
+        let mut keys = self.internal_permit_keys(&account_id);
+        keys.insert(&public_key);
+        self.permit_keys_of.insert(&account_id, &keys);
+        self.permit_keys.insert(&public_key, &account_id);
+
+        Self::emit_permit_key_update(&account_id, &public_key, true);
+        refund_deposit(storage_usage);
+    }
+
+    /// Removes a public key of the caller. The storage deposit of the key is refunded.
+    /// Requires exactly 1 yoctoNEAR attached.
+    #[payable]
+    pub fn permit_remove_key(&mut self, public_key: PublicKey) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.permit_keys.get(&public_key).as_ref() == Some(&account_id),
+            "Public key is not registered for the caller"
+        );
+        let storage_usage = env::storage_usage();
+
+        let mut keys = self.internal_permit_keys(&account_id);
+        keys.remove(&public_key);
+        if keys.is_empty() {
+            self.permit_keys_of.remove(&account_id);
+        } else {
+            self.permit_keys_of.insert(&account_id, &keys);
+        }
+        self.permit_keys.remove(&public_key);
+
+        Self::emit_permit_key_update(&account_id, &public_key, false);
+        let refund = Balance::from(storage_usage.saturating_sub(env::storage_usage()))
+            * env::storage_byte_cost();
+        if refund > 0 {
+            Promise::new(account_id).transfer(refund);
+        }
+    }
+
+    /// Returns all public keys, that can sign permits for the given account.
+    pub fn permit_keys_of(&self, account_id: AccountId) -> Vec<PublicKey> {
+        self.permit_keys_of
+            .get(&account_id)
+            .map(|keys| keys.to_vec())
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    fn internal_permit_keys(&self, account_id: &AccountId) -> UnorderedSet<PublicKey> {
+        self.permit_keys_of.get(account_id).unwrap_or_else(|| {
+            UnorderedSet::new(
+                StorageKey::PermitKeysOf {
+                    account_hash: env::sha256_array(account_id.as_bytes()),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        })
+    }
+
+    fn emit_permit_key_update(account_id: &AccountId, public_key: &PublicKey, registered: bool) {
+        ContractEvent::PermitKeyUpdate(PermitKeyUpdate {
+            account_id,
+            public_key,
+            registered,
+        })
+        .emit();
+    }
+}
+
+/// Verifies an ed25519 signature with the host function of `near_sdk::sys`, since near-sdk 4
+/// does not expose `env::ed25519_verify` yet.
+#[cfg(target_arch = "wasm32")]
+fn ed25519_verify(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+    require!(signature.len() == 64, "Invalid signature length");
+    require!(public_key.len() == 32, "Invalid public key length");
+    unsafe {
+        near_sdk::sys::ed25519_verify(
+            signature.len() as _,
+            signature.as_ptr() as _,
+            message.len() as _,
+            message.as_ptr() as _,
+            public_key.len() as _,
+            public_key.as_ptr() as _,
+        ) == 1
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn ed25519_verify(_signature: &[u8], _message: &[u8], _public_key: &[u8]) -> bool {
+    env::panic_str("ed25519_verify is not supported by the mocked blockchain")
+}
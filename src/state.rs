@@ -0,0 +1,174 @@
+//! Versioned contract state.
+//!
+//! The state version is stored under its own storage key, so that it can be read before the
+//! layout of the contract state is known. State without a stored version is the v1 state, which
+//! has initially been deployed on mainnet.
+//!
+//! Whenever the layout of [`Contract`] changes:
+//! 1. copy the current layout of [`Contract`] into a new `ContractVn` struct in this module,
+//! 2. add a variant for it to [`VersionedContract`] and let the previous version migrate into it,
+//! 3. let `ContractVn` migrate into [`Contract`] and increment [`STATE_VERSION`].
+//!
+//! As long as the current state version has not been deployed on mainnet, its layout can still
+//! be changed without adding a new version.
+
+use crate::multisig::Multisig;
+use crate::upgrade::DEFAULT_UPGRADE_DELAY;
+use crate::{Contract, ContractExt, StorageKey};
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_contract_standards::fungible_token::FungibleToken;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LazyOption, LookupMap, TreeMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::{env, near_bindgen, require, AccountId, Balance, StorageUsage};
+
+/// Version of the current [`Contract`] state layout.
+pub const STATE_VERSION: u32 = 2;
+
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
+#[derive(BorshDeserialize)]
+pub struct ContractV1 {
+    /// Keep track of each account's balances
+    pub accounts: LookupMap<AccountId, Balance>,
+
+    /// Total supply of all tokens.
+    pub total_supply: Balance,
+
+    /// The bytes for the largest possible account ID that can be registered on the contract
+    pub bytes_for_longest_account_id: StorageUsage,
+
+    /// Metadata for the contract itself
+    pub metadata: LazyOption<FungibleTokenMetadata>,
+}
+
+impl ContractV1 {
+    /// The v1 contract has no notion of an owner, so the contract account itself becomes
+    /// the owner. Balances, total supply and storage accounting are carried over as is. Accounts
+    /// registered under v1 start without a storage balance. The holder index stays incomplete,
+    /// until the holders of v1 have been back-filled.
+    fn migrate(self) -> Contract {
+        let token = FungibleToken {
+            accounts: self.accounts,
+            total_supply: self.total_supply,
+            account_storage_usage: self.bytes_for_longest_account_id,
+        };
+
+        Contract {
+            token,
+            metadata: self.metadata,
+            owner_id: Some(env::current_account_id()),
+            proposed_owner_id: None,
+            acl: LookupMap::new(StorageKey::Acl.try_to_vec().unwrap()),
+            max_supply: None,
+            paused: false,
+            frozen_accounts: UnorderedSet::new(StorageKey::FrozenAccounts.try_to_vec().unwrap()),
+            code_hash: None,
+            pending_upgrade: None,
+            upgrade_delay: DEFAULT_UPGRADE_DELAY,
+            pending_upgrade_delay: None,
+            multisig: Multisig::new(),
+            governance_id: None,
+            allowances: LookupMap::new(StorageKey::Allowances.try_to_vec().unwrap()),
+            allowance_payers: LookupMap::new(StorageKey::AllowancePayers.try_to_vec().unwrap()),
+            permit_keys: LookupMap::new(StorageKey::PermitKeyOwners.try_to_vec().unwrap()),
+            permit_keys_of: LookupMap::new(StorageKey::PermitKeys.try_to_vec().unwrap()),
+            permit_nonces: LookupMap::new(StorageKey::PermitNonces.try_to_vec().unwrap()),
+            treasury_reserved: 0,
+            airdrops: Vector::new(StorageKey::Airdrops.try_to_vec().unwrap()),
+            airdrop_claims: LookupMap::new(StorageKey::AirdropClaims.try_to_vec().unwrap()),
+            vestings: Vector::new(StorageKey::Vestings.try_to_vec().unwrap()),
+            vestings_of: LookupMap::new(StorageKey::VestingIds.try_to_vec().unwrap()),
+            vesting_outstanding: 0,
+            lockups: UnorderedMap::new(StorageKey::Lockups.try_to_vec().unwrap()),
+            total_locked: 0,
+            lockup_expiries: TreeMap::new(StorageKey::LockupExpiries.try_to_vec().unwrap()),
+            balance_checkpoints: LookupMap::new(
+                StorageKey::BalanceCheckpoints.try_to_vec().unwrap(),
+            ),
+            supply_checkpoints: Vector::new(StorageKey::SupplyCheckpoints.try_to_vec().unwrap()),
+            delegates: LookupMap::new(StorageKey::Delegates.try_to_vec().unwrap()),
+            vote_checkpoints: LookupMap::new(StorageKey::VoteCheckpoints.try_to_vec().unwrap()),
+            holders: UnorderedSet::new(StorageKey::Holders.try_to_vec().unwrap()),
+            holder_ranking: TreeMap::new(StorageKey::HolderRanking.try_to_vec().unwrap()),
+            holders_supply: 0,
+            holders_complete: self.total_supply == 0,
+            circulation_excluded: UnorderedSet::new(
+                StorageKey::CirculationExcluded.try_to_vec().unwrap(),
+            ),
+            transfer_fee: None,
+            fee_exempt: UnorderedSet::new(StorageKey::FeeExempt.try_to_vec().unwrap()),
+            storage_balances: LookupMap::new(StorageKey::StorageBalances.try_to_vec().unwrap()),
+        }
+    }
+}
+
+/// Contract state of any known version.
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum VersionedContract {
+    V1(ContractV1),
+    V2(Contract),
+}
+
+impl VersionedContract {
+    /// Reads the contract state according to the stored state version.
+    pub fn read() -> Self {
+        match read_state_version() {
+            1 => Self::V1(env::state_read().unwrap()),
+            2 => Self::V2(env::state_read().unwrap()),
+            version if version > STATE_VERSION => env::panic_str(&format!(
+                "Cannot migrate from newer state version {}",
+                version
+            )),
+            version => env::panic_str(&format!("Unknown state version {}", version)),
+        }
+    }
+
+    /// Migrates the state step by step until it has reached the current version.
+    pub fn into_current(self) -> Contract {
+        match self {
+            Self::V1(state) => Self::V2(state.migrate()).into_current(),
+            Self::V2(contract) => contract,
+        }
+    }
+}
+
+impl Contract {
+    /// Checks invariants of the contract state, that must hold after every migration.
+    pub(crate) fn assert_state_invariants(&self) {
+        require!(self.metadata.get().is_some(), "Metadata is missing");
+        require!(
+            self.token.account_storage_usage > 0,
+            "Account storage usage is missing"
+        );
+        if let Some(owner_id) = &self.owner_id {
+            require!(
+                self.token.accounts.get(owner_id).unwrap_or_default() <= self.token.total_supply,
+                "Owner balance exceeds total supply"
+            );
+        }
+        if let Some(max_supply) = self.max_supply {
+            require!(
+                self.token.total_supply <= max_supply,
+                "Total supply exceeds max supply"
+            );
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Returns the version of the contract state layout.
+    pub fn state_version(&self) -> u32 {
+        read_state_version()
+    }
+}
+
+pub(crate) fn read_state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|version| u32::try_from_slice(&version).unwrap())
+        .unwrap_or(1)
+}
+
+pub(crate) fn write_state_version() {
+    env::storage_write(STATE_VERSION_KEY, &STATE_VERSION.try_to_vec().unwrap());
+}
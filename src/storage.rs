@@ -0,0 +1,164 @@
+//! Storage management of the accounts according to NEP-145.
+//!
+//! Registering an account costs the storage of its balance, as in the standard fungible token.
+//! Any deposit above that minimum is kept as the storage balance of the account and pays for the
+//! checkpoints, votes and holder index entries, that the account causes by its calls. Accounts
+//! registered under v1 start without a storage balance. When an account is closed, the minimum
+//! and whatever is left of its storage balance are refunded.
+
+use crate::{Contract, ContractExt};
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, StorageManagement,
+};
+use near_sdk::json_types::U128;
+use near_sdk::{
+    assert_one_yocto, env, log, near_bindgen, require, AccountId, Balance, Promise, StorageUsage,
+};
+
+#[near_bindgen]
+impl StorageManagement for Contract {
+    /// Registers the account with at least the minimum deposit. Unless `registration_only` is
+    /// set, the rest of the deposit, or the whole deposit for a registered account, is added to
+    /// the storage balance of the account. Otherwise it is refunded.
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let registration_only = registration_only.unwrap_or(false);
+        let mut refund = amount;
+        if !self.token.accounts.contains_key(&account_id) {
+            let min_balance = self.storage_balance_bounds().min.0;
+            require!(
+                amount >= min_balance,
+                "The attached deposit is less than the minimum storage balance"
+            );
+            self.token.internal_register_account(&account_id);
+            refund -= min_balance;
+        } else if registration_only {
+            log!("The account is already registered, refunding the deposit");
+        }
+        if !registration_only && refund > 0 {
+            let available = self.storage_balances.get(&account_id).unwrap_or_default();
+            self.storage_balances
+                .insert(&account_id, &(available + refund));
+            refund = 0;
+        }
+        if refund > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+        self.storage_balance_of(account_id).unwrap()
+    }
+
+    /// Withdraws up to `amount` of the available storage balance of the caller, all of it by
+    /// default. Requires exactly 1 yoctoNEAR attached.
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.token.accounts.contains_key(&account_id),
+            format!("The account {} is not registered", account_id)
+        );
+        let available = self.storage_balances.get(&account_id).unwrap_or_default();
+        let amount = amount.map(|amount| amount.0).unwrap_or(available);
+        require!(
+            amount <= available,
+            "The amount is greater than the available storage balance"
+        );
+        if amount > 0 {
+            self.storage_balances
+                .insert(&account_id, &(available - amount));
+            Promise::new(account_id.clone()).transfer(amount);
+        }
+        self.storage_balance_of(account_id).unwrap()
+    }
+
+    /// Closes the account of the caller and refunds the minimum deposit and the remaining
+    /// storage balance. An account with a positive balance can only be closed with `force`,
+    /// which burns its tokens. The receiver of the transfer fee can't be closed. Requires exactly
+    /// 1 yoctoNEAR attached.
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let balance = match self.token.accounts.get(&account_id) {
+            Some(balance) => balance,
+            None => {
+                log!("The account {} is not registered", account_id);
+                return false;
+            }
+        };
+        require!(
+            balance == 0 || force.unwrap_or(false),
+            "Can't unregister the account with the positive balance without force"
+        );
+        require!(
+            self.transfer_fee.as_ref().map(|fee| &fee.receiver_id) != Some(&account_id),
+            "Can't unregister the receiver of the transfer fee"
+        );
+        let storage_usage = env::storage_usage();
+        self.token.accounts.remove(&account_id);
+        self.token.total_supply -= balance;
+        self.on_account_closed(account_id.clone(), balance);
+
+        let cost = Balance::from(env::storage_usage().saturating_sub(storage_usage))
+            * env::storage_byte_cost();
+        let deposit = self.storage_balance_bounds().min.0
+            + self
+                .storage_balances
+                .remove(&account_id)
+                .unwrap_or_default();
+        require!(
+            deposit >= cost,
+            "The storage deposit doesn't cover the storage of closing the account"
+        );
+        Promise::new(account_id).transfer(deposit - cost + 1);
+        true
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: (Balance::from(self.token.account_storage_usage) * env::storage_byte_cost())
+                .into(),
+            max: None,
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        if !self.token.accounts.contains_key(&account_id) {
+            return None;
+        }
+        let available = self.storage_balances.get(&account_id).unwrap_or_default();
+        Some(StorageBalance {
+            total: (self.storage_balance_bounds().min.0 + available).into(),
+            available: available.into(),
+        })
+    }
+}
+
+impl Contract {
+    /// Charges the storage, that has been used since `storage_usage`, to the storage balance of
+    /// the caller. Storage used by the contract's own calls, such as callbacks, is paid by the
+    /// contract.
+    pub(crate) fn internal_charge_storage(&mut self, storage_usage: StorageUsage) {
+        let cost = Balance::from(env::storage_usage().saturating_sub(storage_usage))
+            * env::storage_byte_cost();
+        let payer_id = env::predecessor_account_id();
+        if cost == 0 || payer_id == env::current_account_id() {
+            return;
+        }
+        let available = self.storage_balances.get(&payer_id).unwrap_or_default();
+        require!(
+            available >= cost,
+            format!(
+                "The storage balance of {} doesn't cover {} yoctoNEAR of storage, deposit more via storage_deposit",
+                payer_id, cost
+            )
+        );
+        self.storage_balances.insert(&payer_id, &(available - cost));
+    }
+}
@@ -0,0 +1,45 @@
+//! The treasury is the balance of the contract account itself. Parts of it can be reserved,
+//! e.g. for airdrop campaigns, so that they can't be spent otherwise in the meantime.
+
+use crate::{Contract, ContractExt};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId, Balance};
+
+#[near_bindgen]
+impl Contract {
+    /// Returns the amount of treasury tokens, that are reserved.
+    pub fn ft_treasury_reserved(&self) -> U128 {
+        self.treasury_reserved.into()
+    }
+}
+
+impl Contract {
+    pub(crate) fn treasury_id() -> AccountId {
+        env::current_account_id()
+    }
+
+    /// Asserts, that the treasury has at least the given amount of unreserved tokens.
+    pub(crate) fn assert_treasury_available(&self, amount: Balance) {
+        let balance = self
+            .token
+            .accounts
+            .get(&Self::treasury_id())
+            .unwrap_or_default();
+        require!(
+            balance.saturating_sub(self.treasury_reserved) >= amount,
+            "Not enough unreserved tokens in the treasury"
+        );
+    }
+
+    pub(crate) fn internal_reserve_treasury(&mut self, amount: Balance) {
+        self.assert_treasury_available(amount);
+        self.treasury_reserved += amount;
+    }
+
+    pub(crate) fn internal_release_treasury(&mut self, amount: Balance) {
+        self.treasury_reserved = self
+            .treasury_reserved
+            .checked_sub(amount)
+            .unwrap_or_else(|| env::panic_str("Released more than reserved"));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::acl::Role;
+use crate::event::{ContractEvent, ContractUpgrade, UpgradeUpdate};
+use crate::{Contract, ContractExt};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, CryptoHash, Gas, Promise};
+
+/// Gas, that is kept for the `execute_upgrade` call itself. The rest of the prepaid gas is
+/// forwarded to `migrate`.
+const GAS_FOR_UPGRADE: Gas = Gas(20_000_000_000_000);
+
+/// Default delay between proposing and executing an upgrade: 2 days in nanoseconds.
+pub const DEFAULT_UPGRADE_DELAY: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Maximum upgrade delay: 30 days in nanoseconds. Otherwise a huge delay would block upgrades
+/// forever.
+const MAX_UPGRADE_DELAY: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+/// An upgrade, that has been proposed, but not yet executed.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingUpgrade {
+    /// Base58 encoded sha256 hash of the code, which is shown as `code_hash` by `near state`.
+    pub code_hash: Base58CryptoHash,
+    /// Timestamp in nanoseconds, after which the upgrade can be executed.
+    pub eta: U64,
+}
+
+/// A lower upgrade delay, that only applies once the previous delay has passed.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingUpgradeDelay {
+    pub upgrade_delay: U64,
+    /// Timestamp in nanoseconds, after which the lower delay applies.
+    pub effective_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Proposes an upgrade to the code with the given hash. The upgrade can be executed after
+    /// `eta`, which has to be at least the upgrade delay in the future.
+    /// A pending upgrade needs to be cancelled, before a new one can be proposed.
+    /// Can only be called by an upgrader.
+    #[payable]
+    pub fn propose_upgrade(&mut self, code_hash: Base58CryptoHash, eta: U64) {
+        assert_one_yocto();
+        self.assert_role(Role::Upgrader);
+        self.internal_propose_upgrade(code_hash, eta);
+    }
+
+    /// Cancels the pending upgrade. Can only be called by an upgrader.
+    #[payable]
+    pub fn cancel_upgrade(&mut self) {
+        assert_one_yocto();
+        self.assert_role(Role::Upgrader);
+        let pending_upgrade = self
+            .pending_upgrade
+            .take()
+            .unwrap_or_else(|| env::panic_str("There is no pending upgrade"));
+
+        ContractEvent::UpgradeUpdate(UpgradeUpdate::Cancelled {
+            pending_upgrade: &pending_upgrade,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+
+    /// Executes the pending upgrade with the given code, once its `eta` has passed.
+    /// The code is deployed to the contract account and the state is migrated in the same
+    /// promise batch. If the migration fails, the deployment is reverted as well.
+    /// Can only be called by an upgrader.
+    #[payable]
+    pub fn execute_upgrade(&mut self, code: Base64VecU8) -> Promise {
+        assert_one_yocto();
+        self.assert_role(Role::Upgrader);
+        let pending_upgrade = self
+            .pending_upgrade
+            .take()
+            .unwrap_or_else(|| env::panic_str("There is no pending upgrade"));
+        require!(
+            env::block_timestamp() >= pending_upgrade.eta.0,
+            "Upgrade is still timelocked"
+        );
+
+        let code: Vec<u8> = code.into();
+        let code_hash = env::sha256_array(&code);
+        require!(
+            code_hash == CryptoHash::from(pending_upgrade.code_hash),
+            "Code hash mismatch"
+        );
+        self.internal_upgrade(code, code_hash)
+    }
+
+    /// Sets the minimum delay in nanoseconds between proposing and executing an upgrade.
+    /// Only affects upgrades, that are proposed afterwards. A higher delay applies immediately,
+    /// while a lower delay only applies once the current delay has passed, so that the delay
+    /// can't be lowered right before proposing an upgrade. The delay can be at most 30 days.
+    /// Can only be called by the owner.
+    #[payable]
+    pub fn set_upgrade_delay(&mut self, upgrade_delay: U64) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.internal_set_upgrade_delay(upgrade_delay);
+    }
+
+    pub fn pending_upgrade(&self) -> Option<PendingUpgrade> {
+        self.pending_upgrade.clone()
+    }
+
+    /// Returns the upgrade delay, that currently applies.
+    pub fn upgrade_delay(&self) -> U64 {
+        self.internal_upgrade_delay().into()
+    }
+
+    /// Returns the lower upgrade delay, that has been set, but doesn't apply yet.
+    pub fn pending_upgrade_delay(&self) -> Option<PendingUpgradeDelay> {
+        self.pending_upgrade_delay
+            .clone()
+            .filter(|pending| env::block_timestamp() < pending.effective_at.0)
+    }
+
+    /// Returns the hash of the code, that has last been deployed via `execute_upgrade`.
+    pub fn code_hash(&self) -> Option<Base58CryptoHash> {
+        self.code_hash.map(Base58CryptoHash::from)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_propose_upgrade(&mut self, code_hash: Base58CryptoHash, eta: U64) {
+        require!(
+            self.pending_upgrade.is_none(),
+            "There already is a pending upgrade"
+        );
+        let earliest_eta = env::block_timestamp()
+            .checked_add(self.internal_upgrade_delay())
+            .unwrap_or_else(|| env::panic_str("Upgrade delay overflows the block timestamp"));
+        require!(
+            eta.0 >= earliest_eta,
+            "Upgrade delay has not been respected"
+        );
+
+        let pending_upgrade = PendingUpgrade { code_hash, eta };
+        ContractEvent::UpgradeUpdate(UpgradeUpdate::Proposed {
+            pending_upgrade: &pending_upgrade,
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+        self.pending_upgrade = Some(pending_upgrade);
+    }
+
+    pub(crate) fn internal_set_upgrade_delay(&mut self, upgrade_delay: U64) {
+        require!(
+            upgrade_delay.0 <= MAX_UPGRADE_DELAY,
+            "Upgrade delay cannot exceed 30 days"
+        );
+        let current_delay = self.internal_upgrade_delay();
+        let now = env::block_timestamp();
+        let effective_at = if upgrade_delay.0 >= current_delay {
+            self.upgrade_delay = upgrade_delay.0;
+            self.pending_upgrade_delay = None;
+            now
+        } else {
+            let effective_at = now
+                .checked_add(current_delay)
+                .unwrap_or_else(|| env::panic_str("Upgrade delay overflows the block timestamp"));
+            self.upgrade_delay = current_delay;
+            self.pending_upgrade_delay = Some(PendingUpgradeDelay {
+                upgrade_delay,
+                effective_at: effective_at.into(),
+            });
+            effective_at
+        };
+
+        ContractEvent::UpgradeUpdate(UpgradeUpdate::DelayUpdated {
+            upgrade_delay,
+            effective_at: effective_at.into(),
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+    }
+
+    fn internal_upgrade_delay(&self) -> u64 {
+        match &self.pending_upgrade_delay {
+            Some(pending) if env::block_timestamp() >= pending.effective_at.0 => {
+                pending.upgrade_delay.0
+            }
+            _ => self.upgrade_delay,
+        }
+    }
+
+    pub(crate) fn internal_upgrade(&mut self, code: Vec<u8>, code_hash: CryptoHash) -> Promise {
+        let gas = env::prepaid_gas()
+            .0
+            .checked_sub(env::used_gas().0 + GAS_FOR_UPGRADE.0)
+            .unwrap_or_else(|| env::panic_str("More gas is required"));
+
+        ContractEvent::ContractUpgrade(ContractUpgrade {
+            previous_code_hash: self.code_hash.map(Base58CryptoHash::from),
+            code_hash: code_hash.into(),
+            by: &env::predecessor_account_id(),
+        })
+        .emit();
+        self.code_hash = Some(code_hash);
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), vec![], 0, Gas(gas))
+    }
+}
@@ -0,0 +1,253 @@
+//! Vesting schedules, that release tokens linearly to a beneficiary after a cliff.
+//!
+//! The tokens of a schedule are transferred from the funder to the treasury, where they stay
+//! reserved until the beneficiary claims them or the funder revokes the schedule.
+
+use crate::acl::paginate;
+use crate::allowance::refund_deposit;
+use crate::event::{ContractEvent, VestingUpdate};
+use crate::{Contract, ContractExt, StorageKey};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::Vector;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId, Balance};
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingSchedule {
+    pub id: u64,
+    pub funder: AccountId,
+    pub beneficiary: AccountId,
+    pub total: U128,
+    pub claimed: U128,
+    /// Timestamp in nanoseconds, at which the vesting starts.
+    pub start: U64,
+    /// Duration in nanoseconds after `start`, before which nothing is vested.
+    pub cliff: U64,
+    /// Duration in nanoseconds after `start`, after which everything is vested.
+    pub duration: U64,
+    pub revocable: bool,
+    pub revoked: bool,
+}
+
+impl VestingSchedule {
+    /// Returns the amount of tokens, that are vested at the given timestamp.
+    pub fn vested_at(&self, timestamp: u64) -> Balance {
+        let (total, start, duration) = (self.total.0, self.start.0, self.duration.0);
+        if timestamp < start + self.cliff.0 {
+            0
+        } else if self.revoked || timestamp >= start + duration {
+            total
+        } else {
+            // `total * elapsed / duration` without overflowing
+            let elapsed = (timestamp - start) as u128;
+            let duration = duration as u128;
+            total / duration * elapsed + total % duration * elapsed / duration
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Creates a vesting schedule for the beneficiary, that is funded with `total` tokens of the
+    /// caller. Nothing is vested before `start + cliff`, afterwards the tokens are released
+    /// linearly until everything is vested at `start + duration`. Returns the id of the schedule.
+    ///
+    /// If the schedule is revocable, the caller can revoke it and gets the unvested tokens back.
+    /// Enough deposit for the storage of the schedule needs to be attached, at least 1
+    /// yoctoNEAR. Any deposit, that is not needed, is refunded.
+    #[payable]
+    pub fn create_vesting(
+        &mut self,
+        beneficiary: AccountId,
+        total: U128,
+        start: U64,
+        cliff: U64,
+        duration: U64,
+        revocable: bool,
+    ) -> u64 {
+        require!(
+            env::attached_deposit() >= 1,
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        require!(total.0 > 0, "The amount should be a positive number");
+        require!(duration.0 > 0, "Duration must be positive");
+        require!(cliff.0 <= duration.0, "Cliff must not exceed the duration");
+        require!(
+            start.0.checked_add(duration.0).is_some(),
+            "Vesting must end before the maximum timestamp"
+        );
+        let funder = env::predecessor_account_id();
+        self.assert_transfer_allowed(&funder, &beneficiary);
+        require!(
+            self.token.accounts.contains_key(&beneficiary),
+            format!("The account {} is not registered", beneficiary)
+        );
+        self.assert_spendable(&funder, total.0);
+        let storage_usage = env::storage_usage();
+
+        let treasury_id = Self::treasury_id();
+        if !self.token.accounts.contains_key(&treasury_id) {
+            self.token.internal_register_account(&treasury_id);
+        }
+        let id = self.vestings.len();
+        // the checkpoints of the transfer are charged to the storage balance of the funder
+        let transfer_storage_usage = env::storage_usage();
+        self.internal_transfer(
+            &funder,
+            &treasury_id,
+            total.0,
+            Some(format!("Vesting {}", id)),
+        );
+        let storage_usage =
+            (storage_usage + env::storage_usage()).saturating_sub(transfer_storage_usage);
+        self.internal_reserve_treasury(total.0);
+        self.vesting_outstanding += total.0;
+
+        let schedule = VestingSchedule {
+            id,
+            funder,
+            beneficiary,
+            total,
+            claimed: U128(0),
+            start,
+            cliff,
+            duration,
+            revocable,
+            revoked: false,
+        };
+        self.vestings.push(&schedule);
+        let mut ids = self.internal_vestings_of(&schedule.beneficiary);
+        ids.push(&id);
+        self.vestings_of.insert(&schedule.beneficiary, &ids);
+
+        ContractEvent::VestingUpdate(VestingUpdate::Created {
+            schedule: &schedule,
+        })
+        .emit();
+        refund_deposit(storage_usage);
+        id
+    }
+
+    /// Transfers all vested, but not yet claimed tokens of the schedule to the beneficiary.
+    /// Can only be called by the beneficiary and returns the claimed amount.
+    pub fn vesting_claim(&mut self, id: u64) -> U128 {
+        let mut schedule = self.internal_vesting(id);
+        require!(
+            schedule.beneficiary == env::predecessor_account_id(),
+            "Only the beneficiary can claim"
+        );
+        self.assert_transfer_allowed(&Self::treasury_id(), &schedule.beneficiary);
+        let amount = schedule.vested_at(env::block_timestamp()) - schedule.claimed.0;
+        require!(amount > 0, "Nothing to claim");
+
+        schedule.claimed = (schedule.claimed.0 + amount).into();
+        self.vestings.replace(id, &schedule);
+        self.internal_release_treasury(amount);
+        self.vesting_outstanding -= amount;
+        self.internal_transfer(
+            &Self::treasury_id(),
+            &schedule.beneficiary,
+            amount,
+            Some(format!("Vesting {}", id)),
+        );
+
+        ContractEvent::VestingUpdate(VestingUpdate::Claimed {
+            vesting_id: id,
+            beneficiary: &schedule.beneficiary,
+            amount: amount.into(),
+        })
+        .emit();
+        amount.into()
+    }
+
+    /// Revokes a revocable schedule and refunds the unvested tokens to the funder. Tokens, that
+    /// are already vested, can still be claimed by the beneficiary. Can only be called by the
+    /// funder and requires exactly 1 yoctoNEAR attached. Returns the refunded amount.
+    #[payable]
+    pub fn vesting_revoke(&mut self, id: u64) -> U128 {
+        assert_one_yocto();
+        let mut schedule = self.internal_vesting(id);
+        require!(
+            schedule.funder == env::predecessor_account_id(),
+            "Only the funder can revoke"
+        );
+        require!(schedule.revocable, "Vesting is not revocable");
+        require!(!schedule.revoked, "Vesting has already been revoked");
+        self.assert_transfer_allowed(&Self::treasury_id(), &schedule.funder);
+
+        let vested = schedule.vested_at(env::block_timestamp());
+        let refund = schedule.total.0 - vested;
+        schedule.total = vested.into();
+        schedule.revoked = true;
+        self.vestings.replace(id, &schedule);
+        if refund > 0 {
+            self.internal_release_treasury(refund);
+            self.vesting_outstanding -= refund;
+            self.internal_transfer(
+                &Self::treasury_id(),
+                &schedule.funder,
+                refund,
+                Some(format!("Vesting {} revoked", id)),
+            );
+        }
+
+        ContractEvent::VestingUpdate(VestingUpdate::Revoked {
+            vesting_id: id,
+            refund: refund.into(),
+            by: &schedule.funder,
+        })
+        .emit();
+        refund.into()
+    }
+
+    pub fn vesting(&self, id: u64) -> Option<VestingSchedule> {
+        self.vestings.get(id)
+    }
+
+    /// Returns the amount of tokens, that the beneficiary can currently claim.
+    pub fn vesting_claimable(&self, id: u64) -> U128 {
+        let schedule = self.internal_vesting(id);
+        (schedule.vested_at(env::block_timestamp()) - schedule.claimed.0).into()
+    }
+
+    /// Returns a page of the vesting schedules of the given beneficiary.
+    pub fn vesting_of(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<VestingSchedule> {
+        self.vestings_of
+            .get(&account_id)
+            .map(|ids| {
+                paginate(
+                    ids.iter().map(|id| self.vestings.get(id).unwrap()),
+                    from_index,
+                    limit,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    fn internal_vesting(&self, id: u64) -> VestingSchedule {
+        self.vestings
+            .get(id)
+            .unwrap_or_else(|| env::panic_str("Vesting not found"))
+    }
+
+    fn internal_vestings_of(&self, account_id: &AccountId) -> Vector<u64> {
+        self.vestings_of.get(account_id).unwrap_or_else(|| {
+            Vector::new(
+                StorageKey::VestingIdsOf {
+                    account_hash: env::sha256_array(account_id.as_bytes()),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        })
+    }
+}
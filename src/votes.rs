@@ -0,0 +1,122 @@
+//! Voting power, that holders delegate to themselves or to another account without moving tokens.
+//!
+//! The votes of a delegatee are the sum of the balances of all accounts, that delegate to it.
+//! Tokens of accounts, that have not delegated, don't count as votes, so holders need to
+//! delegate to themselves to vote with their own tokens. The votes follow every change of the
+//! delegators' balances and are recorded as checkpoints for historical queries.
+
+use crate::checkpoint::{assert_not_future, last_value, record, value_at};
+use crate::event::{ContractEvent, DelegateChanged, DelegateVotesChanged};
+use crate::{Contract, ContractExt, StorageKey};
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::collections::Vector;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId, Balance};
+
+#[near_bindgen]
+impl Contract {
+    /// Delegates the voting power of the caller's balance to the delegatee, which may be the
+    /// caller itself. The storage of the new vote checkpoints is charged to the storage balance
+    /// of the caller. Requires exactly 1 yoctoNEAR attached.
+    #[payable]
+    pub fn delegate(&mut self, delegatee: AccountId) {
+        assert_one_yocto();
+        let delegator = env::predecessor_account_id();
+        require!(
+            self.token.accounts.contains_key(&delegator),
+            format!("The account {} is not registered", delegator)
+        );
+        let previous = self.delegates.insert(&delegator, &delegatee);
+        require!(
+            previous.as_ref() != Some(&delegatee),
+            "Votes are already delegated to this account"
+        );
+
+        ContractEvent::DelegateChanged(DelegateChanged {
+            delegator: &delegator,
+            from_delegate: previous.as_ref(),
+            to_delegate: &delegatee,
+        })
+        .emit();
+        let balance = self.token.accounts.get(&delegator).unwrap_or_default();
+        let storage_usage = env::storage_usage();
+        self.internal_move_votes(previous.as_ref(), Some(&delegatee), balance);
+        self.internal_charge_storage(storage_usage);
+    }
+
+    /// Returns the account, that the given account delegates its votes to, if any.
+    pub fn delegates(&self, account_id: AccountId) -> Option<AccountId> {
+        self.delegates.get(&account_id)
+    }
+
+    /// Returns the current votes of the account.
+    pub fn get_votes(&self, account_id: AccountId) -> U128 {
+        self.vote_checkpoints
+            .get(&account_id)
+            .map(|checkpoints| last_value(&checkpoints))
+            .unwrap_or_default()
+            .into()
+    }
+
+    /// Returns the votes of the account at the end of the given block.
+    pub fn get_past_votes(&self, account_id: AccountId, block_height: U64) -> U128 {
+        assert_not_future(block_height.0, env::block_height());
+        self.vote_checkpoints
+            .get(&account_id)
+            .map(|checkpoints| {
+                value_at(&checkpoints, |checkpoint| {
+                    checkpoint.block_height <= block_height.0
+                })
+            })
+            .unwrap_or_default()
+            .into()
+    }
+}
+
+impl Contract {
+    /// Moves `amount` votes from one delegatee to another. Votes from or to no delegatee are
+    /// created or removed.
+    pub(crate) fn internal_move_votes(
+        &mut self,
+        from: Option<&AccountId>,
+        to: Option<&AccountId>,
+        amount: Balance,
+    ) {
+        if amount == 0 || from == to {
+            return;
+        }
+        if let Some(delegatee) = from {
+            self.internal_update_votes(delegatee, |votes| votes - amount);
+        }
+        if let Some(delegatee) = to {
+            self.internal_update_votes(delegatee, |votes| votes + amount);
+        }
+    }
+
+    fn internal_update_votes(
+        &mut self,
+        delegatee: &AccountId,
+        update: impl FnOnce(Balance) -> Balance,
+    ) {
+        let mut checkpoints = self.vote_checkpoints.get(delegatee).unwrap_or_else(|| {
+            Vector::new(
+                StorageKey::VoteCheckpointsOf {
+                    account_hash: env::sha256_array(delegatee.as_bytes()),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        let previous_votes = last_value(&checkpoints);
+        let new_votes = update(previous_votes);
+        record(&mut checkpoints, previous_votes, new_votes);
+        self.vote_checkpoints.insert(delegatee, &checkpoints);
+
+        ContractEvent::DelegateVotesChanged(DelegateVotesChanged {
+            delegate: delegatee,
+            previous_votes: previous_votes.into(),
+            new_votes: new_votes.into(),
+        })
+        .emit();
+    }
+}
@@ -1,22 +1,31 @@
 mod util;
 
+use ed25519_dalek::Signer;
+use fungible_token::merkle::MerkleTree;
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8, U128, U64};
+use serde_json::json;
 use util::*;
+use workspaces::AccountId;
 
 const TOTAL_SUPPLY: u128 = 100_000_000_000_000_000_000_000_000;
+/// Time in nanoseconds, that an upgrade is scheduled ahead of the latest block, so that the
+/// proposal transaction is included before the eta.
+const ETA_MARGIN: u64 = 5_000_000_000;
 
 #[tokio::test]
 async fn test_migrate() -> anyhow::Result<()> {
     let (worker, owner, contract) =
-        initialize_contracts(TOTAL_SUPPLY, Some("./out/fungible_token_old.wasm")).await?;
+        initialize_contracts(TOTAL_SUPPLY, None, Some("./out/fungible_token_old.wasm")).await?;
 
     let user_0 = worker.dev_create_account().await?;
     let user_1 = worker.dev_create_account().await?;
     let user_2 = worker.dev_create_account().await?;
 
     tokio::try_join!(
-        call::storage_deposit(&contract, &user_0, None, Some(true), None),
-        call::storage_deposit(&contract, &user_1, None, Some(true), None),
-        call::storage_deposit(&contract, &user_2, None, Some(true), None)
+        call::storage_deposit(&contract, &user_0, None, None, None),
+        call::storage_deposit(&contract, &user_1, None, None, None),
+        call::storage_deposit(&contract, &user_2, None, None, None)
     )?;
 
     call::ft_transfer(&owner, contract.id(), user_0.id(), 100).await?;
@@ -31,10 +40,14 @@ async fn test_migrate() -> anyhow::Result<()> {
     assert_eq!(balance.0, 300);
     let balance = view::ft_balance_of(&contract, owner.id()).await?;
     assert_eq!(balance.0, TOTAL_SUPPLY - 600);
+    let metadata = view::ft_metadata(&contract).await?;
+    let total_supply = view::ft_total_supply(&contract).await?;
+    assert_eq!(total_supply.0, TOTAL_SUPPLY);
+    let storage_balance_bounds = view::storage_balance_bounds(&contract).await?;
 
     contract
         .as_account()
-        .deploy(include_bytes!("../out/fungible_token.wasm"))
+        .deploy(&read_wasm("./out/fungible_token.wasm").await?)
         .await?
         .into_result()?;
     call::migrate(&contract, contract.as_account()).await?;
@@ -48,5 +61,1488 @@ async fn test_migrate() -> anyhow::Result<()> {
     let balance = view::ft_balance_of(&contract, owner.id()).await?;
     assert_eq!(balance.0, TOTAL_SUPPLY - 600);
 
+    let owner_id = view::owner_get(&contract).await?;
+    assert_eq!(owner_id.as_ref(), Some(contract.id()));
+    let state_version = view::state_version(&contract).await?;
+    assert_eq!(state_version, 2);
+
+    let migrated_total_supply = view::ft_total_supply(&contract).await?;
+    assert_eq!(migrated_total_supply.0, total_supply.0);
+    let migrated_storage_balance_bounds = view::storage_balance_bounds(&contract).await?;
+    assert_eq!(
+        migrated_storage_balance_bounds.min.0,
+        storage_balance_bounds.min.0
+    );
+    // deposits above the minimum are kept as storage balance
+    assert!(migrated_storage_balance_bounds.max.is_none());
+
+    let migrated_metadata = view::ft_metadata(&contract).await?;
+    assert_eq!(migrated_metadata.spec, metadata.spec);
+    assert_eq!(migrated_metadata.name, metadata.name);
+    assert_eq!(migrated_metadata.symbol, metadata.symbol);
+    assert_eq!(migrated_metadata.icon, metadata.icon);
+    assert_eq!(migrated_metadata.reference, metadata.reference);
+    assert_eq!(migrated_metadata.decimals, metadata.decimals);
+
+    // holder queries fail until the holders of v1 have been back-filled by anyone
+    assert!(!view::ft_holders_complete(&contract).await?);
+    assert!(view::ft_holder_count(&contract).await.is_err());
+    assert!(view::ft_top_holders(&contract, Some(2)).await.is_err());
+    let account_ids = [owner.id(), user_0.id(), user_1.id(), user_2.id()];
+    call::storage_deposit(&contract, &user_0, None, None, None).await?;
+    let added = call::holders_backfill(&contract, &user_0, &account_ids[..3]).await?;
+    assert_eq!(added, 3);
+    assert!(!view::ft_holders_complete(&contract).await?);
+    assert!(view::ft_holder_count(&contract).await.is_err());
+    let added = call::holders_backfill(&contract, &user_0, &account_ids).await?;
+    assert_eq!(added, 1);
+    assert!(view::ft_holders_complete(&contract).await?);
+    assert_eq!(view::ft_holder_count(&contract).await?, 4);
+    assert!(call::holders_backfill(&contract, &user_0, &account_ids)
+        .await
+        .is_err());
+    let top_holders = view::ft_top_holders(&contract, Some(2)).await?;
+    assert_eq!(
+        top_holders,
+        vec![
+            (owner.id().clone(), U128(TOTAL_SUPPLY - 600)),
+            (user_2.id().clone(), U128(300))
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrate_from_newer_version() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    worker
+        .patch_state(contract.id(), b"STATE_VERSION", &3u32.try_to_vec()?)
+        .await?;
+    assert_eq!(view::state_version(&contract).await?, 3);
+    assert!(call::migrate(&contract, contract.as_account())
+        .await
+        .is_err());
+    assert_eq!(view::state_version(&contract).await?, 3);
+    let balance = view::ft_balance_of(&contract, owner.id()).await?;
+    assert_eq!(balance.0, TOTAL_SUPPLY);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_icon() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let user = worker.dev_create_account().await?;
+    assert!(call::set_icon(&contract, &user, Some("data:,"))
+        .await
+        .is_err());
+
+    call::set_icon(&contract, &owner, Some("data:,")).await?;
+    let metadata = view::ft_metadata(&contract).await?;
+    assert_eq!(metadata.icon.as_deref(), Some("data:,"));
+
+    assert!(
+        call::set_icon(&contract, &owner, Some("https://walc.io/icon.svg"))
+            .await
+            .is_err()
+    );
+
+    call::set_icon(&contract, &owner, None).await?;
+    let metadata = view::ft_metadata(&contract).await?;
+    assert!(metadata
+        .icon
+        .unwrap()
+        .starts_with("data:image/svg+xml;base64,"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_metadata() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let user = worker.dev_create_account().await?;
+    let mut metadata = view::ft_metadata(&contract).await?;
+    metadata.name = "Walc Token".to_string();
+    assert!(call::set_metadata(&contract, &user, &metadata)
+        .await
+        .is_err());
+    call::set_metadata(&contract, &owner, &metadata).await?;
+    assert_eq!(view::ft_metadata(&contract).await?.name, "Walc Token");
+
+    let mut rescaled = metadata.clone();
+    rescaled.decimals = 18;
+    assert!(call::set_metadata(&contract, &owner, &rescaled)
+        .await
+        .is_err());
+    let mut respecced = metadata;
+    respecced.spec = "ft-2.0.0".to_string();
+    assert!(call::set_metadata(&contract, &owner, &respecced)
+        .await
+        .is_err());
+    assert_eq!(view::ft_metadata(&contract).await?.decimals, 24);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ownership_transfer() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let new_owner = worker.dev_create_account().await?;
+    let user = worker.dev_create_account().await?;
+
+    assert!(call::owner_propose(&contract, &user, user.id())
+        .await
+        .is_err());
+    call::owner_propose(&contract, &owner, new_owner.id()).await?;
+    assert!(call::owner_accept(&contract, &user).await.is_err());
+
+    let owner_id = view::owner_get(&contract).await?;
+    assert_eq!(owner_id.as_ref(), Some(owner.id()));
+
+    call::owner_accept(&contract, &new_owner).await?;
+    let owner_id = view::owner_get(&contract).await?;
+    assert_eq!(owner_id.as_ref(), Some(new_owner.id()));
+
+    assert!(call::owner_renounce(&contract, &owner).await.is_err());
+    call::owner_renounce(&contract, &new_owner).await?;
+    let owner_id = view::owner_get(&contract).await?;
+    assert_eq!(owner_id, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_acl() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let admin = worker.dev_create_account().await?;
+    let user = worker.dev_create_account().await?;
+
+    assert!(call::acl_grant_role(&contract, &user, "admin", user.id())
+        .await
+        .is_err());
+    call::acl_grant_role(&contract, &owner, "admin", admin.id()).await?;
+    call::acl_grant_role(&contract, &admin, "metadata_admin", user.id()).await?;
+
+    assert!(view::acl_has_role(&contract, "metadata_admin", user.id()).await?);
+    assert!(view::acl_has_role(&contract, "metadata_admin", owner.id()).await?);
+    assert!(!view::acl_has_role(&contract, "metadata_admin", admin.id()).await?);
+    let grantees = view::acl_get_grantees(&contract, "metadata_admin").await?;
+    assert_eq!(grantees, vec![user.id().clone()]);
+
+    call::set_icon(&contract, &user, Some("data:,")).await?;
+    assert!(call::set_icon(&contract, &admin, Some("data:,"))
+        .await
+        .is_err());
+
+    call::acl_revoke_role(&contract, &admin, "metadata_admin", user.id()).await?;
+    assert!(!view::acl_has_role(&contract, "metadata_admin", user.id()).await?);
+    assert!(call::set_icon(&contract, &user, Some("data:,"))
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mint() -> anyhow::Result<()> {
+    let (worker, owner, contract) =
+        initialize_contracts(TOTAL_SUPPLY, Some(TOTAL_SUPPLY + 1_000), None).await?;
+
+    let minter = worker.dev_create_account().await?;
+    let user = worker.dev_create_account().await?;
+
+    assert!(call::ft_mint(&contract, &minter, user.id(), 100, None)
+        .await
+        .is_err());
+    call::acl_grant_role(&contract, &owner, "minter", minter.id()).await?;
+
+    assert!(call::ft_mint(&contract, &minter, user.id(), 100, Some(0))
+        .await
+        .is_err());
+    call::ft_mint(&contract, &minter, user.id(), 100, None).await?;
+    assert!(call::ft_mint(&contract, &minter, user.id(), 200, Some(0))
+        .await
+        .is_err());
+    call::ft_mint(&contract, &minter, user.id(), 200, Some(1)).await?;
+
+    let balance = view::ft_balance_of(&contract, user.id()).await?;
+    assert_eq!(balance.0, 300);
+    let total_supply = view::ft_total_supply(&contract).await?;
+    assert_eq!(total_supply.0, TOTAL_SUPPLY + 300);
+
+    assert!(call::ft_mint(&contract, &minter, user.id(), 701, Some(1))
+        .await
+        .is_err());
+    call::ft_mint(&contract, &minter, user.id(), 700, Some(1)).await?;
+    let total_supply = view::ft_total_supply(&contract).await?;
+    assert_eq!(total_supply.0, TOTAL_SUPPLY + 1_000);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_burn() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let burner = worker.dev_create_account().await?;
+
+    call::storage_deposit(&contract, &owner, Some(contract.id()), Some(true), None).await?;
+    call::ft_transfer(&owner, contract.id(), contract.id(), 1_000).await?;
+
+    call::ft_burn(&contract, &owner, 500).await?;
+    let balance = view::ft_balance_of(&contract, owner.id()).await?;
+    assert_eq!(balance.0, TOTAL_SUPPLY - 1_500);
+    assert!(call::ft_burn(&contract, &burner, 1).await.is_err());
+
+    assert!(call::ft_burn_from(&contract, &burner, 400).await.is_err());
+    call::acl_grant_role(&contract, &owner, "burner", burner.id()).await?;
+    call::ft_burn_from(&contract, &burner, 400).await?;
+    let balance = view::ft_balance_of(&contract, contract.id()).await?;
+    assert_eq!(balance.0, 600);
+
+    let total_supply = view::ft_total_supply(&contract).await?;
+    assert_eq!(total_supply.0, TOTAL_SUPPLY - 900);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pause() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let pauser = worker.dev_create_account().await?;
+    let user = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &user, None, None, None).await?;
+
+    assert!(call::pause(&contract, &pauser).await.is_err());
+    call::acl_grant_role(&contract, &owner, "pauser", pauser.id()).await?;
+    call::pause(&contract, &pauser).await?;
+    assert!(view::is_paused(&contract).await?);
+
+    assert!(call::ft_transfer(&owner, contract.id(), user.id(), 100)
+        .await
+        .is_err());
+    assert!(call::ft_mint(&contract, &owner, user.id(), 100, None)
+        .await
+        .is_err());
+    let balance = view::ft_balance_of(&contract, user.id()).await?;
+    assert_eq!(balance.0, 0);
+
+    call::unpause(&contract, &pauser).await?;
+    assert!(!view::is_paused(&contract).await?);
+    call::ft_transfer(&owner, contract.id(), user.id(), 100).await?;
+    let balance = view::ft_balance_of(&contract, user.id()).await?;
+    assert_eq!(balance.0, 100);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transfer_call() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let receiver = initialize_receiver(&worker).await?;
+    let user = worker.dev_create_account().await?;
+    tokio::try_join!(
+        call::storage_deposit(&contract, &user, None, None, None),
+        call::storage_deposit(&contract, &owner, Some(receiver.id()), Some(true), None)
+    )?;
+    call::ft_transfer(&owner, contract.id(), user.id(), 1_000).await?;
+
+    // unused tokens are returned to the sender
+    let res = call::ft_transfer_call(&user, contract.id(), receiver.id(), 100, "30").await?;
+    assert_eq!(res.json::<U128>()?.0, 70);
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 930);
+    assert_eq!(view::ft_balance_of(&contract, receiver.id()).await?.0, 70);
+
+    // all tokens are returned, if the receiver fails
+    let res = call::ft_transfer_call(&user, contract.id(), receiver.id(), 100, "fail").await?;
+    assert_eq!(res.json::<U128>()?.0, 0);
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 930);
+    assert_eq!(view::ft_balance_of(&contract, receiver.id()).await?.0, 70);
+
+    // refunds are resolved, even if the token has been paused in the meantime
+    call::acl_grant_role(&contract, &owner, "pauser", receiver.id()).await?;
+    let res = call::ft_transfer_call(&user, contract.id(), receiver.id(), 100, "pause:100").await?;
+    assert_eq!(res.json::<U128>()?.0, 0);
+    assert!(view::is_paused(&contract).await?);
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 930);
+    assert_eq!(view::ft_balance_of(&contract, receiver.id()).await?.0, 70);
+    assert!(
+        call::ft_transfer_call(&user, contract.id(), receiver.id(), 100, "0")
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_freeze() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let freezer = worker.dev_create_account().await?;
+    let user_0 = worker.dev_create_account().await?;
+    let user_1 = worker.dev_create_account().await?;
+    tokio::try_join!(
+        call::storage_deposit(&contract, &user_0, None, None, None),
+        call::storage_deposit(&contract, &user_1, None, None, None)
+    )?;
+    call::ft_transfer(&owner, contract.id(), user_0.id(), 100).await?;
+
+    assert!(call::freeze_account(&contract, &freezer, user_0.id())
+        .await
+        .is_err());
+    call::acl_grant_role(&contract, &owner, "freezer", freezer.id()).await?;
+    call::freeze_account(&contract, &freezer, user_0.id()).await?;
+    let frozen_accounts = view::frozen_accounts(&contract).await?;
+    assert_eq!(frozen_accounts, vec![user_0.id().clone()]);
+
+    assert!(call::ft_transfer(&user_0, contract.id(), user_1.id(), 50)
+        .await
+        .is_err());
+    assert!(call::ft_transfer(&owner, contract.id(), user_0.id(), 50)
+        .await
+        .is_err());
+    call::ft_transfer(&owner, contract.id(), user_1.id(), 50).await?;
+
+    call::unfreeze_account(&contract, &freezer, user_0.id()).await?;
+    call::ft_transfer(&user_0, contract.id(), user_1.id(), 50).await?;
+    let balance = view::ft_balance_of(&contract, user_1.id()).await?;
+    assert_eq!(balance.0, 100);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_freeze_before_resolve() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let receiver = initialize_receiver(&worker).await?;
+    let user = worker.dev_create_account().await?;
+    tokio::try_join!(
+        call::storage_deposit(&contract, &user, None, None, None),
+        call::storage_deposit(&contract, &owner, Some(receiver.id()), Some(true), None)
+    )?;
+    call::ft_transfer(&owner, contract.id(), user.id(), 100).await?;
+    call::acl_grant_role(&contract, &owner, "freezer", receiver.id()).await?;
+
+    // the receiver freezes the sender and returns part of the tokens, which are still refunded
+    let res = call::ft_transfer_call(&user, contract.id(), receiver.id(), 100, "freeze:60").await?;
+    assert_eq!(res.json::<U128>()?.0, 40);
+    let frozen_accounts = view::frozen_accounts(&contract).await?;
+    assert_eq!(frozen_accounts, vec![user.id().clone()]);
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 60);
+    assert_eq!(view::ft_balance_of(&contract, receiver.id()).await?.0, 40);
+
+    // the refunded tokens stay frozen
+    assert!(call::ft_transfer(&user, contract.id(), owner.id(), 60)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_upgrade() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let upgrader = worker.dev_create_account().await?;
+    let user = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &user, None, None, None).await?;
+    call::ft_transfer(&owner, contract.id(), user.id(), 100).await?;
+
+    let code = read_wasm("./out/fungible_token.wasm").await?;
+    let code_hash = Base58CryptoHash::from(near_sdk::env::sha256_array(&code));
+    let now = worker.view_block().await?.timestamp();
+
+    assert!(call::propose_upgrade(&contract, &upgrader, code_hash, now)
+        .await
+        .is_err());
+    call::acl_grant_role(&contract, &owner, "upgrader", upgrader.id()).await?;
+    assert!(call::propose_upgrade(&contract, &upgrader, code_hash, now)
+        .await
+        .is_err());
+
+    // the delay is capped at 30 days, a higher delay applies immediately
+    let delay = view::upgrade_delay(&contract).await?;
+    assert!(call::set_upgrade_delay(&contract, &owner, u64::MAX)
+        .await
+        .is_err());
+    call::set_upgrade_delay(&contract, &owner, 2 * delay).await?;
+    assert_eq!(view::upgrade_delay(&contract).await?, 2 * delay);
+    assert!(
+        call::propose_upgrade(&contract, &upgrader, code_hash, now + delay + ETA_MARGIN)
+            .await
+            .is_err()
+    );
+    call::propose_upgrade(&contract, &upgrader, code_hash, now + 3 * delay).await?;
+    assert!(view::pending_upgrade(&contract).await?.is_some());
+    assert!(call::execute_upgrade(&contract, &upgrader, code.clone())
+        .await
+        .is_err());
+    call::cancel_upgrade(&contract, &upgrader).await?;
+    assert!(view::pending_upgrade(&contract).await?.is_none());
+
+    // a lower delay only applies once the current delay has passed
+    call::set_upgrade_delay(&contract, &owner, 0).await?;
+    assert_eq!(view::upgrade_delay(&contract).await?, 2 * delay);
+    let pending_delay = view::pending_upgrade_delay(&contract).await?.unwrap();
+    let effective_at: u64 = pending_delay["effective_at"].as_str().unwrap().parse()?;
+    let eta = worker.view_block().await?.timestamp() + ETA_MARGIN;
+    assert!(call::propose_upgrade(&contract, &upgrader, code_hash, eta)
+        .await
+        .is_err());
+    wait_until(&worker, effective_at).await?;
+    assert_eq!(view::upgrade_delay(&contract).await?, 0);
+    assert!(view::pending_upgrade_delay(&contract).await?.is_none());
+
+    let eta = worker.view_block().await?.timestamp() + ETA_MARGIN;
+    call::propose_upgrade(&contract, &upgrader, Base58CryptoHash::default(), eta).await?;
+    wait_until(&worker, eta).await?;
+    assert!(call::execute_upgrade(&contract, &upgrader, code.clone())
+        .await
+        .is_err());
+    call::cancel_upgrade(&contract, &upgrader).await?;
+
+    assert_eq!(view::code_hash(&contract).await?, None);
+    let eta = worker.view_block().await?.timestamp() + ETA_MARGIN;
+    call::propose_upgrade(&contract, &upgrader, code_hash, eta).await?;
+    wait_until(&worker, eta).await?;
+    call::execute_upgrade(&contract, &upgrader, code).await?;
+    assert!(view::pending_upgrade(&contract).await?.is_none());
+    assert_eq!(view::code_hash(&contract).await?, Some(code_hash));
+    assert_eq!(view::state_version(&contract).await?, 2);
+
+    let balance = view::ft_balance_of(&contract, user.id()).await?;
+    assert_eq!(balance.0, 100);
+    let owner_id = view::owner_get(&contract).await?;
+    assert_eq!(owner_id.as_ref(), Some(owner.id()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multisig() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let alice = worker.dev_create_account().await?;
+    let bob = worker.dev_create_account().await?;
+    let carol = worker.dev_create_account().await?;
+    let signers = [alice.id(), bob.id(), carol.id()];
+
+    assert!(call::multisig_setup(&contract, &alice, &signers, 2)
+        .await
+        .is_err());
+    call::multisig_setup(&contract, &owner, &signers, 2).await?;
+    assert!(call::multisig_setup(&contract, &owner, &signers, 1)
+        .await
+        .is_err());
+    assert!(call::pause(&contract, &owner).await.is_err());
+    assert!(!view::acl_has_role(&contract, "pauser", owner.id()).await?);
+
+    assert!(
+        call::propose_action(&contract, &owner, json!({ "type": "pause" }))
+            .await
+            .is_err()
+    );
+    let id = call::propose_action(&contract, &alice, json!({ "type": "pause" })).await?;
+    assert!(call::execute_action(&contract, &alice, id).await.is_err());
+    call::approve_action(&contract, &bob, id).await?;
+    call::execute_action(&contract, &carol, id).await?;
+    assert!(view::is_paused(&contract).await?);
+
+    let id = call::propose_action(&contract, &bob, json!({ "type": "unpause" })).await?;
+    call::approve_action(&contract, &carol, id).await?;
+    call::revoke_approval(&contract, &bob, id).await?;
+    assert!(call::execute_action(&contract, &carol, id).await.is_err());
+    call::approve_action(&contract, &alice, id).await?;
+    call::execute_action(&contract, &alice, id).await?;
+    assert!(!view::is_paused(&contract).await?);
+
+    // owner privileged methods can only be called via multisig actions
+    let upgrade_delay = 2 * view::upgrade_delay(&contract).await?;
+    assert!(call::set_upgrade_delay(&contract, &owner, upgrade_delay)
+        .await
+        .is_err());
+    assert!(call::set_governance(&contract, &owner, Some(owner.id()))
+        .await
+        .is_err());
+    let action = json!({ "type": "set_upgrade_delay", "upgrade_delay": U64(upgrade_delay) });
+    let id = call::propose_action(&contract, &alice, action).await?;
+    call::approve_action(&contract, &carol, id).await?;
+    call::execute_action(&contract, &carol, id).await?;
+    assert_eq!(view::upgrade_delay(&contract).await?, upgrade_delay);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_governance() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let council = worker.dev_create_account().await?;
+    let user = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &user, None, None, None).await?;
+    let dao = initialize_dao(&worker, &[council.id()]).await?;
+    call::storage_deposit(&contract, &owner, Some(dao.id()), None, None).await?;
+
+    assert!(call::set_governance(&contract, &user, Some(dao.id()))
+        .await
+        .is_err());
+    call::set_governance(&contract, &owner, Some(dao.id())).await?;
+    assert_eq!(
+        view::governance_get(&contract).await?.as_ref(),
+        Some(dao.id())
+    );
+    assert!(call::pause(&contract, &owner).await.is_err());
+    assert!(call::ft_mint(&contract, &owner, user.id(), 100, None)
+        .await
+        .is_err());
+    assert!(call::set_governance(&contract, &owner, None).await.is_err());
+
+    let gas = 50_000_000_000_000;
+    let id = call::dao_add_proposal(
+        &dao,
+        &council,
+        contract.id(),
+        "ft_mint",
+        json!({ "account_id": user.id(), "amount": U128(100) }),
+        1,
+        gas,
+    )
+    .await?;
+    call::dao_act_proposal(&dao, &council, id).await?;
+    assert_eq!(view::dao_proposal_status(&dao, id).await?, "Approved");
+    let balance = view::ft_balance_of(&contract, user.id()).await?;
+    assert_eq!(balance.0, 100);
+
+    let id =
+        call::dao_add_proposal(&dao, &council, contract.id(), "pause", json!({}), 1, gas).await?;
+    call::dao_act_proposal(&dao, &council, id).await?;
+    assert!(view::is_paused(&contract).await?);
+
+    let code = read_wasm("./out/fungible_token.wasm").await?;
+    let code_hash = Base58CryptoHash::from(near_sdk::env::sha256_array(&code));
+    let delay = view::upgrade_delay(&contract).await?;
+    let eta = worker.view_block().await?.timestamp() + delay + ETA_MARGIN;
+    let id = call::dao_add_proposal(
+        &dao,
+        &council,
+        contract.id(),
+        "propose_upgrade",
+        json!({ "code_hash": code_hash, "eta": U64(eta) }),
+        1,
+        gas,
+    )
+    .await?;
+    call::dao_act_proposal(&dao, &council, id).await?;
+    assert!(view::pending_upgrade(&contract).await?.is_some());
+    wait_until(&worker, eta).await?;
+    let id = call::dao_add_proposal(
+        &dao,
+        &council,
+        contract.id(),
+        "execute_upgrade",
+        json!({ "code": Base64VecU8::from(code) }),
+        1,
+        200_000_000_000_000,
+    )
+    .await?;
+    call::dao_act_proposal(&dao, &council, id).await?;
+    assert_eq!(view::dao_proposal_status(&dao, id).await?, "Approved");
+    assert_eq!(view::code_hash(&contract).await?, Some(code_hash));
+    assert_eq!(
+        view::governance_get(&contract).await?.as_ref(),
+        Some(dao.id())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_allowance() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let spender = worker.dev_create_account().await?;
+    let user = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &user, None, None, None).await?;
+    let deposit = 10_000_000_000_000_000_000_000;
+
+    assert!(call::ft_approve(&contract, &owner, spender.id(), 100, 0)
+        .await
+        .is_err());
+    call::ft_approve(&contract, &owner, spender.id(), 100, deposit).await?;
+    assert!(
+        call::ft_approve(&contract, &owner, spender.id(), 50, deposit)
+            .await
+            .is_err()
+    );
+    let allowance = view::ft_allowance(&contract, owner.id(), spender.id()).await?;
+    assert_eq!(allowance.0, 100);
+
+    assert!(
+        call::ft_transfer_from(&contract, &spender, owner.id(), user.id(), 101)
+            .await
+            .is_err()
+    );
+    call::ft_transfer_from(&contract, &spender, owner.id(), user.id(), 40).await?;
+    let balance = view::ft_balance_of(&contract, user.id()).await?;
+    assert_eq!(balance.0, 40);
+
+    call::ft_increase_allowance(&contract, &owner, spender.id(), 20, 1).await?;
+    call::ft_decrease_allowance(&contract, &owner, spender.id(), 30).await?;
+    let allowances = view::ft_allowances_of(&contract, owner.id()).await?;
+    assert_eq!(allowances, vec![(spender.id().clone(), U128(50))]);
+
+    call::ft_transfer_from(&contract, &spender, owner.id(), user.id(), 50).await?;
+    assert!(view::ft_allowances_of(&contract, owner.id())
+        .await?
+        .is_empty());
+    assert!(
+        call::ft_transfer_from(&contract, &spender, owner.id(), user.id(), 1)
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_permit() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let relayer = worker.dev_create_account().await?;
+    let spender = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &spender, None, None, None).await?;
+
+    let secret = ed25519_dalek::SecretKey::from_bytes(&[7; 32])?;
+    let keypair = ed25519_dalek::Keypair {
+        public: (&secret).into(),
+        secret,
+    };
+    let public_key =
+        near_sdk::PublicKey::try_from([&[0], keypair.public.as_bytes().as_slice()].concat())?;
+    let sign = |nonce: u64, deadline: u64, amount: u128| -> anyhow::Result<Vec<u8>> {
+        let message = fungible_token::PermitMessage {
+            contract_id: contract.id().as_str().parse()?,
+            spender_id: spender.id().as_str().parse()?,
+            amount,
+            nonce,
+            deadline,
+        };
+        Ok(keypair.sign(&message.try_to_vec()?).to_bytes().to_vec())
+    };
+
+    let sign_key = |account_id: &workspaces::AccountId| -> anyhow::Result<Vec<u8>> {
+        let message = fungible_token::PermitKeyMessage {
+            contract_id: contract.id().as_str().parse()?,
+            account_id: account_id.as_str().parse()?,
+        };
+        Ok(keypair.sign(&message.try_to_vec()?).to_bytes().to_vec())
+    };
+
+    let deadline = worker.view_block().await?.timestamp() + 60 * 60 * 1_000_000_000;
+    let signature = sign(0, deadline, 100)?;
+    assert!(call::ft_permit(
+        &contract,
+        &relayer,
+        &public_key,
+        spender.id(),
+        100,
+        0,
+        deadline,
+        signature.clone()
+    )
+    .await
+    .is_err());
+
+    // the key can't be claimed without a signature for the claiming account
+    assert!(
+        call::permit_add_key(&contract, &relayer, &public_key, sign_key(owner.id())?)
+            .await
+            .is_err()
+    );
+    call::permit_add_key(&contract, &owner, &public_key, sign_key(owner.id())?).await?;
+    assert!(
+        call::permit_add_key(&contract, &relayer, &public_key, sign_key(relayer.id())?)
+            .await
+            .is_err()
+    );
+    assert!(call::ft_permit(
+        &contract,
+        &relayer,
+        &public_key,
+        spender.id(),
+        200,
+        0,
+        deadline,
+        signature.clone()
+    )
+    .await
+    .is_err());
+    call::ft_permit(
+        &contract,
+        &relayer,
+        &public_key,
+        spender.id(),
+        100,
+        0,
+        deadline,
+        signature.clone(),
+    )
+    .await?;
+    assert_eq!(view::ft_permit_nonce(&contract, owner.id()).await?, 1);
+    let allowance = view::ft_allowance(&contract, owner.id(), spender.id()).await?;
+    assert_eq!(allowance.0, 100);
+    assert!(call::ft_permit(
+        &contract,
+        &relayer,
+        &public_key,
+        spender.id(),
+        100,
+        0,
+        deadline,
+        signature
+    )
+    .await
+    .is_err());
+
+    // a permit can't overwrite an existing allowance
+    assert!(call::ft_permit(
+        &contract,
+        &relayer,
+        &public_key,
+        spender.id(),
+        50,
+        1,
+        deadline,
+        sign(1, deadline, 50)?
+    )
+    .await
+    .is_err());
+
+    // the relayer gets the storage deposit of the used up allowance refunded
+    let relayer_balance = relayer.view_account().await?.balance;
+    call::ft_transfer_from(&contract, &spender, owner.id(), spender.id(), 100).await?;
+    let balance = view::ft_balance_of(&contract, spender.id()).await?;
+    assert_eq!(balance.0, 100);
+    assert!(relayer.view_account().await?.balance > relayer_balance);
+
+    let expired = worker.view_block().await?.timestamp();
+    let signature = sign(1, expired, 100)?;
+    assert!(call::ft_permit(
+        &contract,
+        &relayer,
+        &public_key,
+        spender.id(),
+        100,
+        1,
+        expired,
+        signature
+    )
+    .await
+    .is_err());
+
+    call::permit_remove_key(&contract, &owner, &public_key).await?;
+    let signature = sign(1, deadline, 100)?;
+    assert!(call::ft_permit(
+        &contract,
+        &relayer,
+        &public_key,
+        spender.id(),
+        100,
+        1,
+        deadline,
+        signature
+    )
+    .await
+    .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_transfer() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let alice = worker.dev_create_account().await?;
+    let bob = worker.dev_create_account().await?;
+    let carol = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &alice, None, None, None).await?;
+    call::storage_deposit(&contract, &bob, None, None, None).await?;
+
+    let err = call::ft_batch_transfer(
+        &owner,
+        contract.id(),
+        &[(alice.id(), 100), (carol.id(), 200), (bob.id(), 300)],
+    )
+    .await
+    .unwrap_err();
+    assert!(format!("{:?}", err).contains("Transfer 1: receiver"));
+    let balance = view::ft_balance_of(&contract, alice.id()).await?;
+    assert_eq!(balance.0, 0);
+
+    call::ft_batch_transfer(
+        &owner,
+        contract.id(),
+        &[(alice.id(), 100), (bob.id(), 300), (alice.id(), 50)],
+    )
+    .await?;
+    let balance = view::ft_balance_of(&contract, alice.id()).await?;
+    assert_eq!(balance.0, 150);
+    let balance = view::ft_balance_of(&contract, bob.id()).await?;
+    assert_eq!(balance.0, 300);
+    let balance = view::ft_balance_of(&contract, owner.id()).await?;
+    assert_eq!(balance.0, TOTAL_SUPPLY - 450);
+
+    assert!(
+        call::ft_batch_transfer(&alice, contract.id(), &[(bob.id(), 100), (bob.id(), 100)])
+            .await
+            .is_err()
+    );
+    let transfers = vec![(bob.id(), 1); 101];
+    assert!(call::ft_batch_transfer(&owner, contract.id(), &transfers)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_airdrop() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let alice = worker.dev_create_account().await?;
+    let bob = worker.dev_create_account().await?;
+    let carol = worker.dev_create_account().await?;
+    for account in [&alice, &bob, &carol] {
+        call::storage_deposit(&contract, account, None, None, None).await?;
+    }
+    call::storage_deposit(&contract, &owner, Some(contract.id()), Some(true), None).await?;
+    call::ft_transfer(&owner, contract.id(), contract.id(), 1_000).await?;
+
+    let claims = vec![
+        (alice.id().as_str().parse()?, 100),
+        (bob.id().as_str().parse()?, 200),
+        (carol.id().as_str().parse()?, 300),
+    ];
+    let tree = MerkleTree::new(&claims);
+    let root = Base58CryptoHash::from(tree.root());
+    let deadline = worker.view_block().await?.timestamp() + 4 * ETA_MARGIN;
+
+    assert!(call::airdrop_create(&contract, &alice, root, 600, deadline)
+        .await
+        .is_err());
+    assert!(
+        call::airdrop_create(&contract, &owner, root, 1_001, deadline)
+            .await
+            .is_err()
+    );
+    let id = call::airdrop_create(&contract, &owner, root, 600, deadline).await?;
+    assert!(call::ft_burn_from(&contract, &owner, 401).await.is_err());
+
+    let proof = tree.proof(0).unwrap();
+    assert!(call::airdrop_claim(&contract, &alice, id, 200, &proof)
+        .await
+        .is_err());
+    assert!(call::airdrop_claim(&contract, &bob, id, 100, &proof)
+        .await
+        .is_err());
+    call::airdrop_claim(&contract, &alice, id, 100, &proof).await?;
+    assert!(call::airdrop_claim(&contract, &alice, id, 100, &proof)
+        .await
+        .is_err());
+    call::airdrop_claim(&contract, &bob, id, 200, &tree.proof(1).unwrap()).await?;
+    let balance = view::ft_balance_of(&contract, alice.id()).await?;
+    assert_eq!(balance.0, 100);
+    let balance = view::ft_balance_of(&contract, bob.id()).await?;
+    assert_eq!(balance.0, 200);
+
+    assert!(call::airdrop_sweep(&contract, &owner, id).await.is_err());
+    wait_until(&worker, deadline + 1).await?;
+    assert!(
+        call::airdrop_claim(&contract, &carol, id, 300, &tree.proof(2).unwrap())
+            .await
+            .is_err()
+    );
+    call::airdrop_sweep(&contract, &owner, id).await?;
+    call::ft_burn_from(&contract, &owner, 700).await?;
+    let balance = view::ft_balance_of(&contract, contract.id()).await?;
+    assert_eq!(balance.0, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_vesting() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let alice = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &alice, None, None, None).await?;
+
+    let start = worker.view_block().await?.timestamp();
+    let (cliff, duration) = (4 * ETA_MARGIN, 8 * ETA_MARGIN);
+    assert!(
+        call::create_vesting(&contract, &alice, alice.id(), 1, start, 0, duration, false)
+            .await
+            .is_err()
+    );
+    assert!(call::create_vesting(
+        &contract,
+        &owner,
+        alice.id(),
+        8_000,
+        start,
+        duration,
+        cliff,
+        false
+    )
+    .await
+    .is_err());
+    assert!(call::create_vesting(
+        &contract,
+        &owner,
+        alice.id(),
+        8_000,
+        u64::MAX - cliff,
+        cliff,
+        duration,
+        false
+    )
+    .await
+    .is_err());
+    let id = call::create_vesting(
+        &contract,
+        &owner,
+        alice.id(),
+        8_000,
+        start,
+        cliff,
+        duration,
+        false,
+    )
+    .await?;
+    let revocable_id = call::create_vesting(
+        &contract,
+        &owner,
+        alice.id(),
+        8_000,
+        start,
+        cliff,
+        duration,
+        true,
+    )
+    .await?;
+    assert_eq!(view::vesting_of(&contract, alice.id()).await?.len(), 2);
+    let balance = view::ft_balance_of(&contract, contract.id()).await?;
+    assert_eq!(balance.0, 16_000);
+    assert_eq!(view::ft_total_supply(&contract).await?.0, TOTAL_SUPPLY);
+
+    assert!(call::vesting_claim(&contract, &alice, id).await.is_err());
+    assert!(call::vesting_revoke(&contract, &owner, id).await.is_err());
+    assert!(call::vesting_revoke(&contract, &alice, revocable_id)
+        .await
+        .is_err());
+
+    wait_until(&worker, start + cliff).await?;
+    assert!(call::vesting_claim(&contract, &owner, id).await.is_err());
+    let claimed = call::vesting_claim(&contract, &alice, id).await?;
+    assert!((4_000..8_000).contains(&claimed));
+    let refund = call::vesting_revoke(&contract, &owner, revocable_id).await?;
+    assert!(refund > 0 && refund <= 4_000);
+    assert!(call::vesting_revoke(&contract, &owner, revocable_id)
+        .await
+        .is_err());
+
+    wait_until(&worker, start + duration).await?;
+    assert_eq!(
+        view::vesting_claimable(&contract, revocable_id).await?,
+        8_000 - refund
+    );
+    call::vesting_claim(&contract, &alice, id).await?;
+    call::vesting_claim(&contract, &alice, revocable_id).await?;
+    assert!(call::vesting_claim(&contract, &alice, id).await.is_err());
+    let balance = view::ft_balance_of(&contract, alice.id()).await?;
+    assert_eq!(balance.0, 16_000 - refund);
+    let balance = view::ft_balance_of(&contract, contract.id()).await?;
+    assert_eq!(balance.0, 0);
+    assert_eq!(view::ft_treasury_reserved(&contract).await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lockup() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let locker = worker.dev_create_account().await?;
+    let user_0 = worker.dev_create_account().await?;
+    let user_1 = worker.dev_create_account().await?;
+    tokio::try_join!(
+        call::storage_deposit(&contract, &user_0, None, None, None),
+        call::storage_deposit(&contract, &user_1, None, None, None)
+    )?;
+    call::ft_transfer(&owner, contract.id(), user_0.id(), 100).await?;
+
+    let unlock_at = worker.view_block().await?.timestamp() + 4 * ETA_MARGIN;
+    assert!(
+        call::ft_lock(&contract, &locker, user_0.id(), 50, unlock_at)
+            .await
+            .is_err()
+    );
+    call::acl_grant_role(&contract, &owner, "locker", locker.id()).await?;
+    call::ft_lock(&contract, &locker, user_0.id(), 50, unlock_at).await?;
+    assert!(
+        call::ft_lock(&contract, &user_0, user_0.id(), 51, unlock_at)
+            .await
+            .is_err()
+    );
+    call::ft_lock(&contract, &user_0, user_0.id(), 20, unlock_at).await?;
+    let locked = view::ft_locked_balance_of(&contract, user_0.id()).await?;
+    assert_eq!(locked, 70);
+    let spendable = view::ft_spendable_balance_of(&contract, user_0.id()).await?;
+    assert_eq!(spendable, 30);
+    let balance = view::ft_balance_of(&contract, user_0.id()).await?;
+    assert_eq!(balance.0, 100);
+
+    assert!(call::ft_transfer(&user_0, contract.id(), user_1.id(), 31)
+        .await
+        .is_err());
+    assert!(call::ft_burn(&contract, &user_0, 31).await.is_err());
+    call::ft_transfer(&user_0, contract.id(), user_1.id(), 30).await?;
+
+    wait_until(&worker, unlock_at).await?;
+    let locked = view::ft_locked_balance_of(&contract, user_0.id()).await?;
+    assert_eq!(locked, 0);
+    call::ft_transfer(&user_0, contract.id(), user_1.id(), 70).await?;
+    let balance = view::ft_balance_of(&contract, user_1.id()).await?;
+    assert_eq!(balance.0, 100);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_checkpoints() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let alice = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &alice, None, None, None).await?;
+    let before_transfer = worker.view_block().await?.height();
+
+    call::ft_transfer(&owner, contract.id(), alice.id(), 100).await?;
+    let before_burn = worker.view_block().await?.height();
+    call::ft_burn(&contract, &alice, 40).await?;
+    let after_burn = worker.view_block().await?.height();
+
+    let balance = view::ft_balance_of_at(&contract, alice.id(), before_transfer).await?;
+    assert_eq!(balance, 0);
+    let balance = view::ft_balance_of_at(&contract, alice.id(), before_burn).await?;
+    assert_eq!(balance, 100);
+    let balance = view::ft_balance_of_at(&contract, alice.id(), after_burn).await?;
+    assert_eq!(balance, 60);
+    let balance = view::ft_balance_of_at(&contract, owner.id(), before_transfer).await?;
+    assert_eq!(balance, TOTAL_SUPPLY);
+    let balance = view::ft_balance_of_at(&contract, owner.id(), after_burn).await?;
+    assert_eq!(balance, TOTAL_SUPPLY - 100);
+
+    let total_supply = view::ft_total_supply_at(&contract, before_burn).await?;
+    assert_eq!(total_supply, TOTAL_SUPPLY);
+    let total_supply = view::ft_total_supply_at(&contract, after_burn).await?;
+    assert_eq!(total_supply, TOTAL_SUPPLY - 40);
+    assert!(view::ft_total_supply_at(&contract, after_burn + 1_000)
+        .await
+        .is_err());
+
+    // the storage of the checkpoints is paid from the storage balance of the sender
+    let bob = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &bob, None, Some(true), None).await?;
+    let storage_balance = view::storage_balance_of(&contract, bob.id())
+        .await?
+        .unwrap();
+    assert_eq!(storage_balance.available.0, 0);
+    call::ft_transfer(&owner, contract.id(), bob.id(), 10).await?;
+    assert!(call::ft_transfer(&bob, contract.id(), alice.id(), 10)
+        .await
+        .is_err());
+    call::storage_deposit(&contract, &bob, None, None, None).await?;
+    call::ft_transfer(&bob, contract.id(), alice.id(), 10).await?;
+    let storage_balance = view::storage_balance_of(&contract, bob.id())
+        .await?
+        .unwrap();
+    assert!(storage_balance.available.0 < 100_000_000_000_000_000_000_000);
+
+    // the full history is kept
+    let balance = view::ft_balance_of_at(&contract, alice.id(), before_burn).await?;
+    assert_eq!(balance, 100);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delegation() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let alice = worker.dev_create_account().await?;
+    let bob = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &alice, None, None, None).await?;
+    call::ft_transfer(&owner, contract.id(), alice.id(), 100).await?;
+    assert_eq!(view::get_votes(&contract, alice.id()).await?, 0);
+
+    call::delegate(&contract, &alice, alice.id()).await?;
+    assert!(call::delegate(&contract, &alice, alice.id()).await.is_err());
+    assert!(call::delegate(&contract, &bob, bob.id()).await.is_err());
+    assert_eq!(view::get_votes(&contract, alice.id()).await?, 100);
+    call::ft_transfer(&owner, contract.id(), alice.id(), 50).await?;
+    assert_eq!(view::get_votes(&contract, alice.id()).await?, 150);
+    let before_delegation = worker.view_block().await?.height();
+
+    call::delegate(&contract, &alice, bob.id()).await?;
+    let delegatee = view::delegates(&contract, alice.id()).await?;
+    assert_eq!(delegatee, Some(bob.id().clone()));
+    assert_eq!(view::get_votes(&contract, alice.id()).await?, 0);
+    assert_eq!(view::get_votes(&contract, bob.id()).await?, 150);
+    call::ft_burn(&contract, &alice, 30).await?;
+    assert_eq!(view::get_votes(&contract, bob.id()).await?, 120);
+
+    let votes = view::get_past_votes(&contract, alice.id(), before_delegation).await?;
+    assert_eq!(votes, 150);
+    let votes = view::get_past_votes(&contract, bob.id(), before_delegation).await?;
+    assert_eq!(votes, 0);
+
+    // repeated receivers of a batch are only counted once
+    call::storage_deposit(&contract, &bob, None, None, None).await?;
+    call::delegate(&contract, &bob, bob.id()).await?;
+    call::ft_batch_transfer(&owner, contract.id(), &[(bob.id(), 10), (bob.id(), 20)]).await?;
+    let balance = view::ft_balance_of(&contract, bob.id()).await?;
+    assert_eq!(balance.0, 30);
+    assert_eq!(view::get_votes(&contract, bob.id()).await?, 150);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_holders() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let user_0 = worker.dev_create_account().await?;
+    let user_1 = worker.dev_create_account().await?;
+    tokio::try_join!(
+        call::storage_deposit(&contract, &user_0, None, None, None),
+        call::storage_deposit(&contract, &user_1, None, None, None)
+    )?;
+    assert_eq!(view::ft_holder_count(&contract).await?, 1);
+    assert!(view::ft_holders_complete(&contract).await?);
+
+    call::ft_transfer(&owner, contract.id(), user_0.id(), 100).await?;
+    call::ft_transfer(&owner, contract.id(), user_1.id(), 200).await?;
+    assert_eq!(view::ft_holder_count(&contract).await?, 3);
+    let holders = view::ft_holders(&contract).await?;
+    assert!(holders.contains(&(user_0.id().clone(), U128(100))));
+
+    call::ft_transfer(&user_1, contract.id(), user_0.id(), 200).await?;
+    assert_eq!(view::ft_holder_count(&contract).await?, 2);
+    let top_holders = view::ft_top_holders(&contract, None).await?;
+    assert_eq!(
+        top_holders,
+        vec![
+            (owner.id().clone(), U128(TOTAL_SUPPLY - 300)),
+            (user_0.id().clone(), U128(300))
+        ]
+    );
+    assert!(call::holders_backfill(&contract, &user_0, &[user_1.id()])
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_circulating_supply() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let user = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &user, None, None, None).await?;
+    call::storage_deposit(&contract, &owner, Some(contract.id()), Some(true), None).await?;
+    call::ft_transfer(&owner, contract.id(), contract.id(), 1_000).await?;
+    call::ft_transfer(&owner, contract.id(), user.id(), 100).await?;
+    let unlock_at = worker.view_block().await?.timestamp() + 100 * ETA_MARGIN;
+    call::ft_lock(&contract, &user, user.id(), 30, unlock_at).await?;
+    let start = worker.view_block().await?.timestamp();
+    call::create_vesting(
+        &contract,
+        &owner,
+        user.id(),
+        200,
+        start,
+        0,
+        100 * ETA_MARGIN,
+        false,
+    )
+    .await?;
+
+    let supply = view::ft_circulating_supply(&contract).await?;
+    assert_eq!(supply["excluded_accounts"], json!([]));
+    assert_eq!(supply["locked"], json!("30"));
+    assert_eq!(supply["vesting"], json!("200"));
+    let circulating_supply = (TOTAL_SUPPLY - 230).to_string();
+    assert_eq!(supply["circulating_supply"], json!(circulating_supply));
+
+    assert!(
+        call::exclude_from_circulation(&contract, &user, contract.id())
+            .await
+            .is_err()
+    );
+    call::exclude_from_circulation(&contract, &owner, contract.id()).await?;
+    assert!(
+        call::exclude_from_circulation(&contract, &owner, contract.id())
+            .await
+            .is_err()
+    );
+    let supply = view::ft_circulating_supply(&contract).await?;
+    assert_eq!(
+        supply["excluded_accounts"],
+        json!([[contract.id(), "1200"]])
+    );
+    assert_eq!(supply["vesting"], json!("0"));
+    let circulating_supply = (TOTAL_SUPPLY - 1_230).to_string();
+    assert_eq!(supply["circulating_supply"], json!(circulating_supply));
+
+    call::include_in_circulation(&contract, &owner, contract.id()).await?;
+    let supply = view::ft_circulating_supply(&contract).await?;
+    assert_eq!(supply["excluded_accounts"], json!([]));
+
+    // expired lockups don't count, even before they are released or for excluded accounts
+    wait_until(&worker, start + 100 * ETA_MARGIN).await?;
+    let supply = view::ft_circulating_supply(&contract).await?;
+    assert_eq!(supply["locked"], json!("0"));
+    call::exclude_from_circulation(&contract, &owner, user.id()).await?;
+    let supply = view::ft_circulating_supply(&contract).await?;
+    assert_eq!(supply["locked"], json!("0"));
+    let circulating_supply = (TOTAL_SUPPLY - 300).to_string();
+    assert_eq!(supply["circulating_supply"], json!(circulating_supply));
+    call::include_in_circulation(&contract, &owner, user.id()).await?;
+
+    // claimed vesting tokens circulate
+    assert_eq!(call::release_expired_lockups(&contract, &owner).await?, 1);
+    assert_eq!(call::release_expired_lockups(&contract, &owner).await?, 0);
+    assert_eq!(call::vesting_claim(&contract, &user, 0).await?, 200);
+    let supply = view::ft_circulating_supply(&contract).await?;
+    assert_eq!(supply["locked"], json!("0"));
+    assert_eq!(supply["vesting"], json!("0"));
+    assert_eq!(
+        supply["circulating_supply"],
+        json!(TOTAL_SUPPLY.to_string())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transfer_fee() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let user = worker.dev_create_account().await?;
+    let collector = worker.dev_create_account().await?;
+    call::storage_deposit(&contract, &user, None, None, None).await?;
+
+    assert!(
+        call::set_transfer_fee(&contract, &owner, Some((100, 500, collector.id())))
+            .await
+            .is_err()
+    );
+    call::storage_deposit(&contract, &collector, None, None, None).await?;
+    assert!(
+        call::set_transfer_fee(&contract, &user, Some((100, 500, collector.id())))
+            .await
+            .is_err()
+    );
+    assert!(
+        call::set_transfer_fee(&contract, &owner, Some((10_000, 500, collector.id())))
+            .await
+            .is_err()
+    );
+    call::set_transfer_fee(&contract, &owner, Some((100, 500, collector.id()))).await?;
+
+    // 1% of the amount is routed to the fee receiver
+    call::ft_transfer(&owner, contract.id(), user.id(), 10_000).await?;
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 9_900);
+    assert_eq!(view::ft_balance_of(&contract, collector.id()).await?.0, 100);
+
+    // the fee is capped
+    call::ft_transfer(&owner, contract.id(), user.id(), 100_000).await?;
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 109_400);
+    assert_eq!(view::ft_balance_of(&contract, collector.id()).await?.0, 600);
+
+    // the fee can't be routed to a frozen fee receiver, which also can't be closed
+    call::freeze_account(&contract, &owner, collector.id()).await?;
+    assert!(call::ft_transfer(&owner, contract.id(), user.id(), 10_000)
+        .await
+        .is_err());
+    call::unfreeze_account(&contract, &owner, collector.id()).await?;
+    assert!(call::storage_unregister(&contract, &collector, true)
+        .await
+        .is_err());
+
+    // transfers to the fee receiver and of exempt accounts are free
+    call::ft_transfer(&user, contract.id(), collector.id(), 400).await?;
+    assert_eq!(
+        view::ft_balance_of(&contract, collector.id()).await?.0,
+        1_000
+    );
+    assert!(call::fee_exempt_add(&contract, &user, user.id())
+        .await
+        .is_err());
+    call::fee_exempt_add(&contract, &owner, user.id()).await?;
+    call::ft_transfer(&owner, contract.id(), user.id(), 10_000).await?;
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 119_000);
+
+    call::set_transfer_fee(&contract, &owner, None).await?;
+    call::ft_transfer(&collector, contract.id(), owner.id(), 1_000).await?;
+    assert_eq!(view::ft_balance_of(&contract, collector.id()).await?.0, 0);
+    assert_eq!(view::ft_total_supply(&contract).await?.0, TOTAL_SUPPLY);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transfer_fee_legs() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let receiver = initialize_receiver(&worker).await?;
+    let user = worker.dev_create_account().await?;
+    let spender = worker.dev_create_account().await?;
+    let collector = worker.dev_create_account().await?;
+    tokio::try_join!(
+        call::storage_deposit(&contract, &user, None, None, None),
+        call::storage_deposit(&contract, &spender, None, None, None),
+        call::storage_deposit(&contract, &collector, None, None, None),
+        call::storage_deposit(&contract, &owner, Some(receiver.id()), Some(true), None)
+    )?;
+    call::ft_transfer(&owner, contract.id(), user.id(), 100_000).await?;
+    call::set_transfer_fee(&contract, &owner, Some((100, 500, collector.id()))).await?;
+    let legs = |logs: Vec<&str>| -> anyhow::Result<Vec<_>> {
+        Ok(event::ft_transfers(&logs)?
+            .into_iter()
+            .map(|data| (data.old_owner_id, data.new_owner_id, data.amount, data.memo))
+            .collect())
+    };
+    let leg = |from: &AccountId, to: &AccountId, amount: &str, memo: Option<&str>| {
+        (
+            from.to_string(),
+            to.to_string(),
+            amount.to_string(),
+            memo.map(str::to_string),
+        )
+    };
+
+    // the receiver returns half of the net amount and gets half of the fee refunded
+    let res = call::ft_transfer_call(&user, contract.id(), receiver.id(), 10_000, "4950").await?;
+    assert_eq!(res.json::<U128>()?.0, 4_950);
+    assert_eq!(
+        legs(res.logs())?,
+        vec![
+            leg(user.id(), receiver.id(), "9900", None),
+            leg(user.id(), collector.id(), "100", Some("Transfer fee")),
+            leg(receiver.id(), user.id(), "4950", Some("refund")),
+            leg(collector.id(), user.id(), "50", Some("Transfer fee refund")),
+        ]
+    );
+
+    // every transfer of a batch is charged
+    let res = call::ft_batch_transfer(
+        &user,
+        contract.id(),
+        &[(spender.id(), 1_000), (receiver.id(), 2_000)],
+    )
+    .await?;
+    assert_eq!(
+        legs(res.logs())?,
+        vec![
+            leg(user.id(), spender.id(), "990", None),
+            leg(user.id(), collector.id(), "10", Some("Transfer fee")),
+            leg(user.id(), receiver.id(), "1980", None),
+            leg(user.id(), collector.id(), "20", Some("Transfer fee")),
+        ]
+    );
+
+    // the fee is part of the used allowance
+    call::ft_approve(
+        &contract,
+        &user,
+        spender.id(),
+        1_000,
+        10_000_000_000_000_000_000_000,
+    )
+    .await?;
+    let res = call::ft_transfer_from(&contract, &spender, user.id(), owner.id(), 1_000).await?;
+    assert_eq!(
+        legs(res.logs())?,
+        vec![
+            leg(user.id(), owner.id(), "990", None),
+            leg(user.id(), collector.id(), "10", Some("Transfer fee")),
+        ]
+    );
+    assert_eq!(
+        view::ft_allowance(&contract, user.id(), spender.id())
+            .await?
+            .0,
+        0
+    );
+
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 91_000);
+    assert_eq!(view::ft_balance_of(&contract, collector.id()).await?.0, 90);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transfer_call_resolve_gas() -> anyhow::Result<()> {
+    let (worker, owner, contract) = initialize_contracts(TOTAL_SUPPLY, None, None).await?;
+
+    let receiver = initialize_receiver(&worker).await?;
+    let user = worker.dev_create_account().await?;
+    let collector = worker.dev_create_account().await?;
+    tokio::try_join!(
+        call::storage_deposit(&contract, &user, None, None, None),
+        call::storage_deposit(&contract, &collector, None, None, None),
+        call::storage_deposit(&contract, &owner, Some(receiver.id()), Some(true), None)
+    )?;
+    call::ft_transfer(&owner, contract.id(), user.id(), 100_000).await?;
+    tokio::try_join!(
+        call::delegate(&contract, &user, user.id()),
+        call::delegate(&contract, receiver.as_account(), receiver.id())
+    )?;
+    call::set_transfer_fee(&contract, &owner, Some((100, 500, collector.id()))).await?;
+
+    // the refund records checkpoints, holders and votes of all accounts and refunds half of
+    // the fee within the static gas of `ft_resolve_transfer`
+    let res = call::ft_transfer_call(&user, contract.id(), receiver.id(), 10_000, "4950").await?;
+    assert_eq!(res.json::<U128>()?.0, 4_950);
+    let resolve = res
+        .receipt_outcomes()
+        .iter()
+        .filter(|outcome| &outcome.executor_id == contract.id())
+        .last()
+        .unwrap();
+    assert!(resolve.is_success());
+    assert!(resolve.gas_burnt < 20_000_000_000_000);
+
+    assert_eq!(view::ft_balance_of(&contract, user.id()).await?.0, 95_000);
+    assert_eq!(
+        view::ft_balance_of(&contract, receiver.id()).await?.0,
+        4_950
+    );
+    assert_eq!(view::ft_balance_of(&contract, collector.id()).await?.0, 50);
+    assert_eq!(view::get_votes(&contract, user.id()).await?, 95_000);
+    assert_eq!(view::get_votes(&contract, receiver.id()).await?, 4_950);
+    assert_eq!(view::ft_holder_count(&contract).await?, 4);
+
     Ok(())
 }
@@ -1,5 +1,9 @@
 use super::log_tx_result;
-use near_sdk::json_types::U128;
+use fungible_token::merkle::MerkleProof;
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8, U128, U64};
+use near_sdk::PublicKey;
+use serde_json::json;
 use workspaces::{
     result::{ExecutionResult, Value},
     types::Balance,
@@ -32,8 +36,25 @@ pub async fn storage_deposit(
         sender
             .call(contract.id(), "storage_deposit")
             .args_json((account_id, registration_only))
-            .deposit(deposit.unwrap_or(10_000_000_000_000_000_000_000))
+            .deposit(deposit.unwrap_or(100_000_000_000_000_000_000_000))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn storage_unregister(
+    contract: &Contract,
+    sender: &Account,
+    force: bool,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("storage_unregister"),
+        sender
+            .call(contract.id(), "storage_unregister")
+            .args_json((force,))
             .max_gas()
+            .deposit(1)
             .transact()
             .await?,
     )
@@ -56,3 +77,916 @@ pub async fn ft_transfer(
             .await?,
     )
 }
+
+pub async fn ft_transfer_call(
+    sender: &Account,
+    token_id: &AccountId,
+    receiver_id: &AccountId,
+    amount: u128,
+    msg: &str,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_transfer_call"),
+        sender
+            .call(token_id, "ft_transfer_call")
+            .args_json((receiver_id, U128(amount), Option::<String>::None, msg))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn set_icon(
+    contract: &Contract,
+    sender: &Account,
+    icon: Option<&str>,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("set_icon"),
+        sender
+            .call(contract.id(), "set_icon")
+            .args_json(json!({ "icon": icon }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn set_metadata(
+    contract: &Contract,
+    sender: &Account,
+    metadata: &FungibleTokenMetadata,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("set_metadata"),
+        sender
+            .call(contract.id(), "set_metadata")
+            .args_json(json!({ "metadata": metadata }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn owner_propose(
+    contract: &Contract,
+    sender: &Account,
+    new_owner: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("owner_propose"),
+        sender
+            .call(contract.id(), "owner_propose")
+            .args_json(json!({ "new_owner": new_owner }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn owner_accept(
+    contract: &Contract,
+    sender: &Account,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("owner_accept"),
+        sender
+            .call(contract.id(), "owner_accept")
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn owner_renounce(
+    contract: &Contract,
+    sender: &Account,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("owner_renounce"),
+        sender
+            .call(contract.id(), "owner_renounce")
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn acl_grant_role(
+    contract: &Contract,
+    sender: &Account,
+    role: &str,
+    account_id: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("acl_grant_role"),
+        sender
+            .call(contract.id(), "acl_grant_role")
+            .args_json(json!({ "role": role, "account_id": account_id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn acl_revoke_role(
+    contract: &Contract,
+    sender: &Account,
+    role: &str,
+    account_id: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("acl_revoke_role"),
+        sender
+            .call(contract.id(), "acl_revoke_role")
+            .args_json(json!({ "role": role, "account_id": account_id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_mint(
+    contract: &Contract,
+    sender: &Account,
+    account_id: &AccountId,
+    amount: u128,
+    deposit: Option<Balance>,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_mint"),
+        sender
+            .call(contract.id(), "ft_mint")
+            .args_json((account_id, U128(amount), Option::<String>::None))
+            .deposit(deposit.unwrap_or(10_000_000_000_000_000_000_000))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_burn(
+    contract: &Contract,
+    sender: &Account,
+    amount: u128,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_burn"),
+        sender
+            .call(contract.id(), "ft_burn")
+            .args_json((U128(amount), Option::<String>::None))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_burn_from(
+    contract: &Contract,
+    sender: &Account,
+    amount: u128,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_burn_from"),
+        sender
+            .call(contract.id(), "ft_burn_from")
+            .args_json((U128(amount), Option::<String>::None))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn pause(
+    contract: &Contract,
+    sender: &Account,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("pause"),
+        sender
+            .call(contract.id(), "pause")
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn unpause(
+    contract: &Contract,
+    sender: &Account,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("unpause"),
+        sender
+            .call(contract.id(), "unpause")
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn freeze_account(
+    contract: &Contract,
+    sender: &Account,
+    account_id: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("freeze_account"),
+        sender
+            .call(contract.id(), "freeze_account")
+            .args_json((account_id,))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn unfreeze_account(
+    contract: &Contract,
+    sender: &Account,
+    account_id: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("unfreeze_account"),
+        sender
+            .call(contract.id(), "unfreeze_account")
+            .args_json((account_id,))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn propose_upgrade(
+    contract: &Contract,
+    sender: &Account,
+    code_hash: Base58CryptoHash,
+    eta: u64,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("propose_upgrade"),
+        sender
+            .call(contract.id(), "propose_upgrade")
+            .args_json(json!({ "code_hash": code_hash, "eta": U64(eta) }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn cancel_upgrade(
+    contract: &Contract,
+    sender: &Account,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("cancel_upgrade"),
+        sender
+            .call(contract.id(), "cancel_upgrade")
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn execute_upgrade(
+    contract: &Contract,
+    sender: &Account,
+    code: Vec<u8>,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("execute_upgrade"),
+        sender
+            .call(contract.id(), "execute_upgrade")
+            .args_json(json!({ "code": Base64VecU8::from(code) }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn set_upgrade_delay(
+    contract: &Contract,
+    sender: &Account,
+    upgrade_delay: u64,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("set_upgrade_delay"),
+        sender
+            .call(contract.id(), "set_upgrade_delay")
+            .args_json(json!({ "upgrade_delay": U64(upgrade_delay) }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn multisig_setup(
+    contract: &Contract,
+    sender: &Account,
+    signers: &[&AccountId],
+    threshold: u32,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("multisig_setup"),
+        sender
+            .call(contract.id(), "multisig_setup")
+            .args_json(json!({ "signers": signers, "threshold": threshold }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn propose_action(
+    contract: &Contract,
+    sender: &Account,
+    action: serde_json::Value,
+) -> anyhow::Result<u64> {
+    let res = log_tx_result(
+        Some("propose_action"),
+        sender
+            .call(contract.id(), "propose_action")
+            .args_json(json!({ "action": action }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn approve_action(
+    contract: &Contract,
+    sender: &Account,
+    id: u64,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("approve_action"),
+        sender
+            .call(contract.id(), "approve_action")
+            .args_json(json!({ "id": id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn revoke_approval(
+    contract: &Contract,
+    sender: &Account,
+    id: u64,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("revoke_approval"),
+        sender
+            .call(contract.id(), "revoke_approval")
+            .args_json(json!({ "id": id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn execute_action(
+    contract: &Contract,
+    sender: &Account,
+    id: u64,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("execute_action"),
+        sender
+            .call(contract.id(), "execute_action")
+            .args_json(json!({ "id": id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn set_governance(
+    contract: &Contract,
+    sender: &Account,
+    governance_id: Option<&AccountId>,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("set_governance"),
+        sender
+            .call(contract.id(), "set_governance")
+            .args_json(json!({ "governance_id": governance_id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+/// Adds a DAO proposal, that calls `method_name` on `receiver_id` with the given JSON args.
+pub async fn dao_add_proposal(
+    dao: &Contract,
+    sender: &Account,
+    receiver_id: &AccountId,
+    method_name: &str,
+    args: serde_json::Value,
+    deposit: Balance,
+    gas: u64,
+) -> anyhow::Result<u64> {
+    let res = log_tx_result(
+        Some("add_proposal"),
+        sender
+            .call(dao.id(), "add_proposal")
+            .args_json(json!({
+                "proposal": {
+                    "description": method_name,
+                    "kind": {
+                        "FunctionCall": {
+                            "receiver_id": receiver_id,
+                            "actions": [{
+                                "method_name": method_name,
+                                "args": Base64VecU8::from(serde_json::to_vec(&args)?),
+                                "deposit": U128(deposit),
+                                "gas": U64(gas),
+                            }],
+                        },
+                    },
+                },
+            }))
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn dao_act_proposal(
+    dao: &Contract,
+    sender: &Account,
+    id: u64,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("act_proposal"),
+        sender
+            .call(dao.id(), "act_proposal")
+            .args_json(json!({ "id": id, "action": "VoteApprove" }))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_approve(
+    contract: &Contract,
+    sender: &Account,
+    spender_id: &AccountId,
+    amount: u128,
+    deposit: Balance,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_approve"),
+        sender
+            .call(contract.id(), "ft_approve")
+            .args_json(json!({ "spender_id": spender_id, "amount": U128(amount) }))
+            .max_gas()
+            .deposit(deposit)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_increase_allowance(
+    contract: &Contract,
+    sender: &Account,
+    spender_id: &AccountId,
+    amount: u128,
+    deposit: Balance,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_increase_allowance"),
+        sender
+            .call(contract.id(), "ft_increase_allowance")
+            .args_json(json!({ "spender_id": spender_id, "amount": U128(amount) }))
+            .max_gas()
+            .deposit(deposit)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_decrease_allowance(
+    contract: &Contract,
+    sender: &Account,
+    spender_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_decrease_allowance"),
+        sender
+            .call(contract.id(), "ft_decrease_allowance")
+            .args_json(json!({ "spender_id": spender_id, "amount": U128(amount) }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_transfer_from(
+    contract: &Contract,
+    sender: &Account,
+    owner_id: &AccountId,
+    receiver_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_transfer_from"),
+        sender
+            .call(contract.id(), "ft_transfer_from")
+            .args_json(
+                json!({ "owner_id": owner_id, "receiver_id": receiver_id, "amount": U128(amount) }),
+            )
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn permit_add_key(
+    contract: &Contract,
+    sender: &Account,
+    public_key: &PublicKey,
+    signature: Vec<u8>,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("permit_add_key"),
+        sender
+            .call(contract.id(), "permit_add_key")
+            .args_json(json!({
+                "public_key": public_key,
+                "signature": Base64VecU8::from(signature),
+            }))
+            .max_gas()
+            .deposit(10_000_000_000_000_000_000_000)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn permit_remove_key(
+    contract: &Contract,
+    sender: &Account,
+    public_key: &PublicKey,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("permit_remove_key"),
+        sender
+            .call(contract.id(), "permit_remove_key")
+            .args_json(json!({ "public_key": public_key }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn ft_permit(
+    contract: &Contract,
+    sender: &Account,
+    public_key: &PublicKey,
+    spender_id: &AccountId,
+    amount: u128,
+    nonce: u64,
+    deadline: u64,
+    signature: Vec<u8>,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_permit"),
+        sender
+            .call(contract.id(), "ft_permit")
+            .args_json(json!({
+                "public_key": public_key,
+                "spender_id": spender_id,
+                "amount": U128(amount),
+                "nonce": U64(nonce),
+                "deadline": U64(deadline),
+                "signature": Base64VecU8::from(signature),
+            }))
+            .max_gas()
+            .deposit(10_000_000_000_000_000_000_000)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn ft_batch_transfer(
+    sender: &Account,
+    token_id: &AccountId,
+    transfers: &[(&AccountId, u128)],
+) -> anyhow::Result<ExecutionResult<Value>> {
+    let transfers: Vec<_> = transfers
+        .iter()
+        .map(|(receiver_id, amount)| (receiver_id, U128(*amount), Option::<String>::None))
+        .collect();
+    log_tx_result(
+        Some("ft_batch_transfer"),
+        sender
+            .call(token_id, "ft_batch_transfer")
+            .args_json(json!({ "transfers": transfers }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn airdrop_create(
+    contract: &Contract,
+    sender: &Account,
+    merkle_root: Base58CryptoHash,
+    amount: u128,
+    deadline: u64,
+) -> anyhow::Result<u64> {
+    let res = log_tx_result(
+        Some("airdrop_create"),
+        sender
+            .call(contract.id(), "airdrop_create")
+            .args_json(json!({
+                "merkle_root": merkle_root,
+                "amount": U128(amount),
+                "deadline": U64(deadline),
+            }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn airdrop_claim(
+    contract: &Contract,
+    sender: &Account,
+    campaign_id: u64,
+    amount: u128,
+    proof: &MerkleProof,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("airdrop_claim"),
+        sender
+            .call(contract.id(), "airdrop_claim")
+            .args_json(json!({
+                "campaign_id": campaign_id,
+                "amount": U128(amount),
+                "proof": proof,
+            }))
+            .max_gas()
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn airdrop_sweep(
+    contract: &Contract,
+    sender: &Account,
+    campaign_id: u64,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("airdrop_sweep"),
+        sender
+            .call(contract.id(), "airdrop_sweep")
+            .args_json(json!({ "campaign_id": campaign_id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_vesting(
+    contract: &Contract,
+    sender: &Account,
+    beneficiary: &AccountId,
+    total: u128,
+    start: u64,
+    cliff: u64,
+    duration: u64,
+    revocable: bool,
+) -> anyhow::Result<u64> {
+    let res = log_tx_result(
+        Some("create_vesting"),
+        sender
+            .call(contract.id(), "create_vesting")
+            .args_json(json!({
+                "beneficiary": beneficiary,
+                "total": U128(total),
+                "start": U64(start),
+                "cliff": U64(cliff),
+                "duration": U64(duration),
+                "revocable": revocable,
+            }))
+            .max_gas()
+            .deposit(10_000_000_000_000_000_000_000)
+            .transact()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn vesting_claim(contract: &Contract, sender: &Account, id: u64) -> anyhow::Result<u128> {
+    let res = log_tx_result(
+        Some("vesting_claim"),
+        sender
+            .call(contract.id(), "vesting_claim")
+            .args_json(json!({ "id": id }))
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn vesting_revoke(
+    contract: &Contract,
+    sender: &Account,
+    id: u64,
+) -> anyhow::Result<u128> {
+    let res = log_tx_result(
+        Some("vesting_revoke"),
+        sender
+            .call(contract.id(), "vesting_revoke")
+            .args_json(json!({ "id": id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn ft_lock(
+    contract: &Contract,
+    sender: &Account,
+    account_id: &AccountId,
+    amount: u128,
+    unlock_at: u64,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("ft_lock"),
+        sender
+            .call(contract.id(), "ft_lock")
+            .args_json(json!({
+                "account_id": account_id,
+                "amount": U128(amount),
+                "unlock_at": U64(unlock_at),
+            }))
+            .max_gas()
+            .deposit(10_000_000_000_000_000_000_000)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn delegate(
+    contract: &Contract,
+    sender: &Account,
+    delegatee: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("delegate"),
+        sender
+            .call(contract.id(), "delegate")
+            .args_json(json!({ "delegatee": delegatee }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn holders_backfill(
+    contract: &Contract,
+    sender: &Account,
+    account_ids: &[&AccountId],
+) -> anyhow::Result<u64> {
+    let res = log_tx_result(
+        Some("holders_backfill"),
+        sender
+            .call(contract.id(), "holders_backfill")
+            .args_json(json!({ "account_ids": account_ids }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn release_expired_lockups(contract: &Contract, sender: &Account) -> anyhow::Result<u64> {
+    let res = log_tx_result(
+        Some("release_expired_lockups"),
+        sender
+            .call(contract.id(), "release_expired_lockups")
+            .max_gas()
+            .transact()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn exclude_from_circulation(
+    contract: &Contract,
+    sender: &Account,
+    account_id: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("exclude_from_circulation"),
+        sender
+            .call(contract.id(), "exclude_from_circulation")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn include_in_circulation(
+    contract: &Contract,
+    sender: &Account,
+    account_id: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("include_in_circulation"),
+        sender
+            .call(contract.id(), "include_in_circulation")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn set_transfer_fee(
+    contract: &Contract,
+    sender: &Account,
+    transfer_fee: Option<(u16, u128, &AccountId)>,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    let transfer_fee = transfer_fee.map(|(basis_points, max_fee, receiver_id)| {
+        json!({
+            "basis_points": basis_points,
+            "max_fee": U128(max_fee),
+            "receiver_id": receiver_id,
+        })
+    });
+    log_tx_result(
+        Some("set_transfer_fee"),
+        sender
+            .call(contract.id(), "set_transfer_fee")
+            .args_json(json!({ "transfer_fee": transfer_fee }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
+
+pub async fn fee_exempt_add(
+    contract: &Contract,
+    sender: &Account,
+    account_id: &AccountId,
+) -> anyhow::Result<ExecutionResult<Value>> {
+    log_tx_result(
+        Some("fee_exempt_add"),
+        sender
+            .call(contract.id(), "fee_exempt_add")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .deposit(1)
+            .transact()
+            .await?,
+    )
+}
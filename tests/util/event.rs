@@ -7,6 +7,7 @@ use std::fmt::{self, Display, Formatter};
 #[serde(rename_all = "kebab-case")]
 pub enum ContractEvent {
     Nep141(Nep141Event),
+    Walc(WalcEvent),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -16,11 +17,27 @@ pub struct Nep141Event {
     pub event_kind: Nep141EventKind,
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct WalcEvent {
+    pub version: String,
+    pub event: String,
+    pub data: serde_json::Value,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(tag = "event", content = "data")]
 #[serde(rename_all = "snake_case")]
 pub enum Nep141EventKind {
+    FtMint(Vec<FtMintData>),
     FtTransfer(Vec<FtTransferData>),
+    FtBurn(Vec<FtBurnData>),
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FtMintData {
+    pub owner_id: String,
+    pub amount: String,
+    pub memo: Option<String>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -31,10 +48,35 @@ pub struct FtTransferData {
     pub memo: Option<String>,
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FtBurnData {
+    pub owner_id: String,
+    pub amount: String,
+    pub memo: Option<String>,
+}
+
+/// Returns the data of all `ft_transfer` events in the given logs.
+pub fn ft_transfers(logs: &[&str]) -> anyhow::Result<Vec<FtTransferData>> {
+    let mut transfers = vec![];
+    for log in logs {
+        if let Some(event) = log.strip_prefix("EVENT_JSON:") {
+            if let ContractEvent::Nep141(Nep141Event {
+                event_kind: Nep141EventKind::FtTransfer(datas),
+                ..
+            }) = serde_json::from_str(event)?
+            {
+                transfers.extend(datas);
+            }
+        }
+    }
+    Ok(transfers)
+}
+
 impl Display for ContractEvent {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
             ContractEvent::Nep141(event) => formatter.write_fmt(format_args!("{}", event)),
+            ContractEvent::Walc(event) => formatter.write_fmt(format_args!("{}", event)),
         }
     }
 }
@@ -42,9 +84,15 @@ impl Display for ContractEvent {
 impl Display for Nep141Event {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match &self.event_kind {
+            Nep141EventKind::FtMint(_) => {
+                formatter.write_fmt(format_args!("{}: ft_mint", "event".bright_cyan()))?;
+            }
             Nep141EventKind::FtTransfer(_) => {
                 formatter.write_fmt(format_args!("{}: ft_transfer", "event".bright_cyan()))?;
             }
+            Nep141EventKind::FtBurn(_) => {
+                formatter.write_fmt(format_args!("{}: ft_burn", "event".bright_cyan()))?;
+            }
         }
         formatter.write_fmt(format_args!("\n{}: nep141", "standard".bright_cyan(),))?;
         formatter.write_fmt(format_args!(
@@ -53,16 +101,40 @@ impl Display for Nep141Event {
             self.version
         ))?;
         match &self.event_kind {
+            Nep141EventKind::FtMint(datas) => {
+                for data in datas {
+                    formatter.write_fmt(format_args!("\n{}: {}", "data".bright_cyan(), data))?;
+                }
+            }
             Nep141EventKind::FtTransfer(datas) => {
                 for data in datas {
                     formatter.write_fmt(format_args!("\n{}: {}", "data".bright_cyan(), data))?;
                 }
             }
+            Nep141EventKind::FtBurn(datas) => {
+                for data in datas {
+                    formatter.write_fmt(format_args!("\n{}: {}", "data".bright_cyan(), data))?;
+                }
+            }
         }
         Ok(())
     }
 }
 
+impl Display for WalcEvent {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_fmt(format_args!("{}: {}", "event".bright_cyan(), self.event))?;
+        formatter.write_fmt(format_args!("\n{}: walc", "standard".bright_cyan()))?;
+        formatter.write_fmt(format_args!(
+            "\n{}: {}",
+            "version".bright_cyan(),
+            self.version
+        ))?;
+        formatter.write_fmt(format_args!("\n{}: {}", "data".bright_cyan(), self.data))?;
+        Ok(())
+    }
+}
+
 impl Display for FtTransferData {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         if let Some(memo) = &self.memo {
@@ -84,3 +156,45 @@ impl Display for FtTransferData {
         Ok(())
     }
 }
+
+impl Display for FtMintData {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(memo) = &self.memo {
+            formatter.write_fmt(format_args!(
+                "{} ({}) --> {}",
+                self.amount.bright_blue(),
+                memo,
+                self.owner_id.bright_blue(),
+            ))?;
+        } else {
+            formatter.write_fmt(format_args!(
+                "{} --> {}",
+                self.amount.bright_blue(),
+                self.owner_id.bright_blue(),
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for FtBurnData {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(memo) = &self.memo {
+            formatter.write_fmt(format_args!(
+                "{} --> {} ({}) --> {}",
+                self.owner_id.bright_blue(),
+                self.amount.bright_blue(),
+                memo,
+                "burn".bright_red(),
+            ))?;
+        } else {
+            formatter.write_fmt(format_args!(
+                "{} --> {} --> {}",
+                self.owner_id.bright_blue(),
+                self.amount.bright_blue(),
+                "burn".bright_red(),
+            ))?;
+        }
+        Ok(())
+    }
+}
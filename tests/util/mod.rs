@@ -2,15 +2,17 @@ pub mod call;
 pub mod event;
 pub mod view;
 
+use anyhow::Context;
 use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 use near_sdk::json_types::U128;
 use owo_colors::OwoColorize;
+use serde_json::json;
 use tokio::fs;
 use workspaces::{
     network::Sandbox,
     result::{ExecutionFinalResult, ExecutionResult, Value, ViewResultDetails},
     types::{KeyType, SecretKey},
-    Account, Contract, Worker,
+    Account, AccountId, Contract, Worker,
 };
 
 #[macro_export]
@@ -36,8 +38,32 @@ macro_rules! print_log {
     };
 }
 
+/// Reads a WASM binary from `out/`, which is created by the build scripts.
+pub async fn read_wasm(path: &str) -> anyhow::Result<Vec<u8>> {
+    fs::read(path)
+        .await
+        .with_context(|| format!("{} is missing, run ./build_local.sh first", path))
+}
+
+/// Produces blocks until the latest block timestamp has passed the given timestamp.
+/// The number of blocks, that are fast forwarded at once, is estimated from the previous
+/// blocks, so that long delays don't need to be waited for block by block.
+pub async fn wait_until(worker: &Worker<Sandbox>, timestamp: u64) -> anyhow::Result<()> {
+    let mut now = worker.view_block().await?.timestamp();
+    let mut blocks = 1;
+    while now < timestamp {
+        worker.fast_forward(blocks).await?;
+        let next = worker.view_block().await?.timestamp();
+        let block_time = ((next - now) / blocks).max(1);
+        blocks = ((timestamp.saturating_sub(next)) / block_time).max(1);
+        now = next;
+    }
+    Ok(())
+}
+
 pub async fn initialize_contracts(
     total_supply: u128,
+    max_supply: Option<u128>,
     path: Option<&'static str>,
 ) -> anyhow::Result<(Worker<Sandbox>, Account, Contract)> {
     let worker = workspaces::sandbox().await?;
@@ -49,17 +75,17 @@ pub async fn initialize_contracts(
         .create_tla_and_deploy(
             "ft.test.near".parse()?,
             key,
-            &fs::read(path.unwrap_or("./out/fungible_token.wasm")).await?,
+            &read_wasm(path.unwrap_or("./out/fungible_token.wasm")).await?,
         )
         .await?
         .into_result()?;
 
     contract
         .call("new")
-        .args_json((
-            owner.id(),
-            U128(total_supply),
-            FungibleTokenMetadata {
+        .args_json(json!({
+            "owner_id": owner.id(),
+            "total_supply": U128(total_supply),
+            "metadata": FungibleTokenMetadata {
                 spec: FT_METADATA_SPEC.to_string(),
                 name: "WALC".to_string(),
                 symbol: "WALC".to_string(),
@@ -68,12 +94,23 @@ pub async fn initialize_contracts(
                 reference_hash: None,
                 decimals: 24,
             },
-        ))
+            "max_supply": max_supply.map(U128),
+        }))
         .max_gas()
         .transact()
         .await?
         .into_result()?;
 
+    // storage balance of the owner, that pays for the checkpoints of its transfers
+    call::storage_deposit(
+        &contract,
+        &owner,
+        None,
+        None,
+        Some(1_000_000_000_000_000_000_000_000),
+    )
+    .await?;
+
     Ok((worker, owner, contract))
 }
 
@@ -123,3 +160,30 @@ pub fn log_view_result(res: ViewResultDetails) -> anyhow::Result<ViewResultDetai
     }
     Ok(res)
 }
+
+/// Deploys the DAO stand-in with the given council.
+pub async fn initialize_dao(
+    worker: &Worker<Sandbox>,
+    council: &[&AccountId],
+) -> anyhow::Result<Contract> {
+    let dao = worker
+        .dev_deploy(&read_wasm("./out/dao.wasm").await?)
+        .await?;
+
+    dao.call("new")
+        .args_json(json!({ "council": council }))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    Ok(dao)
+}
+
+/// Deploys the `ft_on_transfer` receiver stub.
+pub async fn initialize_receiver(worker: &Worker<Sandbox>) -> anyhow::Result<Contract> {
+    let receiver = worker
+        .dev_deploy(&read_wasm("./out/receiver.wasm").await?)
+        .await?;
+    Ok(receiver)
+}
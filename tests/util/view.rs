@@ -1,5 +1,8 @@
 use super::log_view_result;
-use near_sdk::json_types::U128;
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_contract_standards::storage_management::{StorageBalance, StorageBalanceBounds};
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
+use serde_json::json;
 use workspaces::{AccountId, Contract};
 
 pub async fn ft_balance_of(contract: &Contract, account_id: &AccountId) -> anyhow::Result<U128> {
@@ -13,3 +16,375 @@ pub async fn ft_balance_of(contract: &Contract, account_id: &AccountId) -> anyho
     )?;
     Ok(res.json()?)
 }
+
+pub async fn ft_metadata(contract: &Contract) -> anyhow::Result<FungibleTokenMetadata> {
+    let res = log_view_result(contract.call("ft_metadata").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn owner_get(contract: &Contract) -> anyhow::Result<Option<AccountId>> {
+    let res = log_view_result(contract.call("owner_get").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn acl_has_role(
+    contract: &Contract,
+    role: &str,
+    account_id: &AccountId,
+) -> anyhow::Result<bool> {
+    let res = log_view_result(
+        contract
+            .call("acl_has_role")
+            .args_json(json!({ "role": role, "account_id": account_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn acl_get_grantees(contract: &Contract, role: &str) -> anyhow::Result<Vec<AccountId>> {
+    let res = log_view_result(
+        contract
+            .call("acl_get_grantees")
+            .args_json(json!({ "role": role }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_total_supply(contract: &Contract) -> anyhow::Result<U128> {
+    let res = log_view_result(contract.call("ft_total_supply").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn is_paused(contract: &Contract) -> anyhow::Result<bool> {
+    let res = log_view_result(contract.call("is_paused").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn frozen_accounts(contract: &Contract) -> anyhow::Result<Vec<AccountId>> {
+    let res = log_view_result(
+        contract
+            .call("frozen_accounts")
+            .args_json(json!({}))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn state_version(contract: &Contract) -> anyhow::Result<u32> {
+    let res = log_view_result(contract.call("state_version").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn code_hash(contract: &Contract) -> anyhow::Result<Option<Base58CryptoHash>> {
+    let res = log_view_result(contract.call("code_hash").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn storage_balance_bounds(contract: &Contract) -> anyhow::Result<StorageBalanceBounds> {
+    let res = log_view_result(
+        contract
+            .call("storage_balance_bounds")
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn storage_balance_of(
+    contract: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<Option<StorageBalance>> {
+    let res = log_view_result(
+        contract
+            .call("storage_balance_of")
+            .args_json((account_id,))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn pending_upgrade(contract: &Contract) -> anyhow::Result<Option<serde_json::Value>> {
+    let res = log_view_result(contract.call("pending_upgrade").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn upgrade_delay(contract: &Contract) -> anyhow::Result<u64> {
+    let res = log_view_result(contract.call("upgrade_delay").max_gas().view().await?)?;
+    Ok(res.json::<U64>()?.0)
+}
+
+pub async fn pending_upgrade_delay(
+    contract: &Contract,
+) -> anyhow::Result<Option<serde_json::Value>> {
+    let res = log_view_result(
+        contract
+            .call("pending_upgrade_delay")
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn governance_get(contract: &Contract) -> anyhow::Result<Option<AccountId>> {
+    let res = log_view_result(contract.call("governance_get").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn dao_proposal_status(dao: &Contract, id: u64) -> anyhow::Result<String> {
+    let res = log_view_result(
+        dao.call("get_proposal")
+            .args_json(json!({ "id": id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    let proposal: serde_json::Value = res.json()?;
+    Ok(proposal["status"].as_str().unwrap_or_default().to_string())
+}
+
+pub async fn ft_allowance(
+    contract: &Contract,
+    owner_id: &AccountId,
+    spender_id: &AccountId,
+) -> anyhow::Result<U128> {
+    let res = log_view_result(
+        contract
+            .call("ft_allowance")
+            .args_json(json!({ "owner_id": owner_id, "spender_id": spender_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_allowances_of(
+    contract: &Contract,
+    owner_id: &AccountId,
+) -> anyhow::Result<Vec<(AccountId, U128)>> {
+    let res = log_view_result(
+        contract
+            .call("ft_allowances_of")
+            .args_json(json!({ "owner_id": owner_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_permit_nonce(contract: &Contract, account_id: &AccountId) -> anyhow::Result<u64> {
+    let res = log_view_result(
+        contract
+            .call("ft_permit_nonce")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U64>()?.0)
+}
+
+pub async fn vesting_claimable(contract: &Contract, id: u64) -> anyhow::Result<u128> {
+    let res = log_view_result(
+        contract
+            .call("vesting_claimable")
+            .args_json(json!({ "id": id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn vesting_of(
+    contract: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    let res = log_view_result(
+        contract
+            .call("vesting_of")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_treasury_reserved(contract: &Contract) -> anyhow::Result<u128> {
+    let res = log_view_result(
+        contract
+            .call("ft_treasury_reserved")
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn ft_locked_balance_of(
+    contract: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<u128> {
+    let res = log_view_result(
+        contract
+            .call("ft_locked_balance_of")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn ft_spendable_balance_of(
+    contract: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<u128> {
+    let res = log_view_result(
+        contract
+            .call("ft_spendable_balance_of")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn ft_balance_of_at(
+    contract: &Contract,
+    account_id: &AccountId,
+    block_height: u64,
+) -> anyhow::Result<u128> {
+    let res = log_view_result(
+        contract
+            .call("ft_balance_of_at")
+            .args_json(json!({ "account_id": account_id, "block_height": U64(block_height) }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn ft_total_supply_at(contract: &Contract, block_height: u64) -> anyhow::Result<u128> {
+    let res = log_view_result(
+        contract
+            .call("ft_total_supply_at")
+            .args_json(json!({ "block_height": U64(block_height) }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn delegates(
+    contract: &Contract,
+    account_id: &AccountId,
+) -> anyhow::Result<Option<AccountId>> {
+    let res = log_view_result(
+        contract
+            .call("delegates")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn get_votes(contract: &Contract, account_id: &AccountId) -> anyhow::Result<u128> {
+    let res = log_view_result(
+        contract
+            .call("get_votes")
+            .args_json(json!({ "account_id": account_id }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn get_past_votes(
+    contract: &Contract,
+    account_id: &AccountId,
+    block_height: u64,
+) -> anyhow::Result<u128> {
+    let res = log_view_result(
+        contract
+            .call("get_past_votes")
+            .args_json(json!({ "account_id": account_id, "block_height": U64(block_height) }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json::<U128>()?.0)
+}
+
+pub async fn ft_holders(contract: &Contract) -> anyhow::Result<Vec<(AccountId, U128)>> {
+    let res = log_view_result(
+        contract
+            .call("ft_holders")
+            .args_json(json!({}))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_holder_count(contract: &Contract) -> anyhow::Result<u64> {
+    let res = log_view_result(contract.call("ft_holder_count").max_gas().view().await?)?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_holders_complete(contract: &Contract) -> anyhow::Result<bool> {
+    let res = log_view_result(
+        contract
+            .call("ft_holders_complete")
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_top_holders(
+    contract: &Contract,
+    limit: Option<u64>,
+) -> anyhow::Result<Vec<(AccountId, U128)>> {
+    let res = log_view_result(
+        contract
+            .call("ft_top_holders")
+            .args_json(json!({ "limit": limit }))
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}
+
+pub async fn ft_circulating_supply(contract: &Contract) -> anyhow::Result<serde_json::Value> {
+    let res = log_view_result(
+        contract
+            .call("ft_circulating_supply")
+            .max_gas()
+            .view()
+            .await?,
+    )?;
+    Ok(res.json()?)
+}